@@ -1,12 +1,18 @@
 //! Collections
-//! 
-//! Only available when `collection`-feature is active as they rely on third-party Rust crates.
-//! 
-//! Available collections:
-//! * Hashtable: [hashbrown](https://github.com/rust-lang/hashbrown)
+//!
+//! Contains all collections of `alloc` and the following additions:
+//! * [RingBuffer]
+//! * Hashtable: [hashbrown](https://github.com/rust-lang/hashbrown), only available when `collection`-feature is active as it relies on a third-party Rust crate.
 
+#[cfg(feature = "collections")]
 extern crate hashbrown;
 
+mod ring_buffer;
+
+pub use alloc::collections::*;
+pub use ring_buffer::RingBuffer;
+
+#[cfg(feature = "collections")]
 pub use hashbrown::{
     hash_map, hash_set, hash_table, DefaultHashBuilder, Equivalent, HashMap, HashSet, HashTable,
     TryReserveError,
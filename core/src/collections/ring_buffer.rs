@@ -0,0 +1,187 @@
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// A fixed-capacity FIFO queue which overwrites its oldest element when full.
+///
+/// Useful for input histories or audio sample queues where memory must stay bounded.
+///
+/// # Example
+///
+/// ```
+/// use wut::collections::RingBuffer;
+///
+/// let mut buffer = RingBuffer::new(3);
+/// for i in 1..=5 {
+///     buffer.push(i);
+/// }
+///
+/// assert!(buffer.is_full());
+/// assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), [3, 4, 5]);
+/// assert_eq!(buffer.pop(), Some(3));
+/// assert_eq!(buffer.len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RingBuffer<T> {
+    buffer: Vec<Option<T>>,
+    /// Index of the oldest element.
+    head: usize,
+    len: usize,
+}
+
+impl<T> RingBuffer<T> {
+    /// Create an empty buffer holding up to `capacity` elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be greater than zero");
+
+        let mut buffer = Vec::with_capacity(capacity);
+        buffer.resize_with(capacity, || None);
+
+        Self {
+            buffer,
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Append `value` as the newest element.
+    ///
+    /// If the buffer is full, the oldest element is removed and returned.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        let tail = (self.head + self.len) % self.capacity();
+        let old = self.buffer[tail].replace(value);
+
+        if self.is_full() {
+            self.head = (self.head + 1) % self.capacity();
+        } else {
+            self.len += 1;
+        }
+
+        old
+    }
+
+    /// Remove and return the oldest element.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let value = self.buffer[self.head].take();
+        self.head = (self.head + 1) % self.capacity();
+        self.len -= 1;
+
+        value
+    }
+
+    /// The oldest element.
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// The newest element.
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len.checked_sub(1)?)
+    }
+
+    /// The `index`-th element counted from the oldest.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        self.buffer[(self.head + index) % self.capacity()].as_ref()
+    }
+
+    /// Iterate from the oldest to the newest element.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            buffer: self,
+            front: 0,
+            back: self.len,
+        }
+    }
+
+    /// Remove all elements.
+    pub fn clear(&mut self) {
+        self.buffer.iter_mut().for_each(|v| *v = None);
+        self.head = 0;
+        self.len = 0;
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+/// Iterator over the elements of a [RingBuffer] from oldest to newest.
+pub struct Iter<'a, T> {
+    buffer: &'a RingBuffer<T>,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        let value = self.buffer.get(self.front);
+        self.front += 1;
+        value
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        self.buffer.get(self.back)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+impl<T> FusedIterator for Iter<'_, T> {}
+
+impl<'a, T> IntoIterator for &'a RingBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> Extend<T> for RingBuffer<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use super::{DynamicLoadingError, Module};
+use crate::sync::{ConstMutex, LazyLock, Mutex};
+use alloc::collections::BTreeSet;
+use core::{ffi, marker::PhantomData};
+use wut_sys as sys;
+
+/// Number of instructions which are overwritten at the start of a hooked function.
+const PATCH_LEN: usize = 4;
+/// Bytes reserved for every trampoline: the moved instructions plus a jump back.
+const SLOT_SIZE: u32 = 0x20;
+
+const BLR: u32 = 0x4E80_0020;
+
+/// Trampoline slots in use, counted from the end of the codegen area.
+static SLOTS: ConstMutex<BTreeSet<u32>> = LazyLock::new(|| Mutex::new(BTreeSet::new()));
+
+/// An installed function hook. The original function is restored on drop.
+///
+/// The first four instructions of the exported function are overwritten with an absolute jump to the replacement. The overwritten instructions are moved into a trampoline in the codegen (JIT) area of the application, followed by a jump back into the original function. Calling the trampoline therefore behaves like calling the unhooked function.
+///
+/// # Example
+///
+/// ```
+/// use wut::dynload::{Hook, Module};
+///
+/// type GetTitleId = unsafe extern "C" fn() -> u64;
+///
+/// static mut ORIGINAL: Option<GetTitleId> = None;
+///
+/// unsafe extern "C" fn get_title_id() -> u64 {
+///     let id = unsafe { ORIGINAL.unwrap()() };
+///     println!("OSGetTitleID() = {id:016x}");
+///     id
+/// }
+///
+/// let coreinit = Module::new("coreinit.rpl")?;
+/// let hook = unsafe { Hook::install(&coreinit, "OSGetTitleID", get_title_id as *const ())? };
+/// unsafe { ORIGINAL = Some(core::mem::transmute(hook.original())) };
+///
+/// // every caller of OSGetTitleID now goes through `get_title_id`
+///
+/// drop(hook);
+/// ```
+pub struct Hook<'lib> {
+    target: *mut u32,
+    original: [u32; PATCH_LEN],
+    slot: u32,
+    trampoline: *const (),
+    _marker: PhantomData<&'lib Module>,
+}
+
+impl<'lib> Hook<'lib> {
+    /// Redirect all calls of the function `symbol` exported by `module` to `replacement`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the symbol does not exist, if the application has no free space in its codegen area (see `codegen_size` in `cos.xml`) or if the start of the function cannot be moved, e.g. because it is too short or contains relative branches.
+    ///
+    /// # Safety
+    ///
+    /// This rewrites code which is shared by every thread and core of the application. The caller has to make sure that
+    ///
+    /// - the code of `module` is writable. CafeOS maps code read-only, so this requires an environment which grants access to it (e.g. as a plugin loaded by a homebrew environment). Otherwise the write causes a DSI exception.
+    /// - `replacement` points to an `extern "C"` function with exactly the same signature as `symbol`.
+    /// - the function is not executed by any other thread while the hook is installed or removed.
+    /// - the function is not hooked a second time while this hook exists, and hooks of the same function are dropped in reverse order of installation.
+    /// - [original][Hook::original] is not called after the hook was dropped.
+    pub unsafe fn install(
+        module: &'lib Module,
+        symbol: &str,
+        replacement: *const (),
+    ) -> Result<Self, DynamicLoadingError> {
+        let target = unsafe { module.function::<()>(symbol)?.into_raw() } as *mut u32;
+
+        let original: [u32; PATCH_LEN] = unsafe { core::ptr::read(target as *const _) };
+        if original[..PATCH_LEN - 1].contains(&BLR) {
+            return Err(DynamicLoadingError::Unhookable("function is too short"));
+        }
+        if original
+            .iter()
+            .any(|&instruction| is_relative_branch(instruction))
+        {
+            return Err(DynamicLoadingError::Unhookable(
+                "function starts with a relative branch",
+            ));
+        }
+
+        // moved instructions followed by a jump behind the patch
+        let mut trampoline = [0u32; SLOT_SIZE as usize / 4];
+        trampoline[..PATCH_LEN].copy_from_slice(&original);
+        trampoline[PATCH_LEN..PATCH_LEN * 2]
+            .copy_from_slice(&jump(target.wrapping_add(PATCH_LEN) as u32));
+
+        let (slot, address) = allocate_slot()?;
+        let copied = unsafe {
+            sys::OSCodegenCopy(
+                address as *mut ffi::c_void,
+                trampoline.as_mut_ptr() as *mut ffi::c_void,
+                SLOT_SIZE as _,
+            )
+        };
+        if copied == 0 {
+            free_slot(slot);
+            return Err(DynamicLoadingError::Unhookable(
+                "trampoline cannot be written to the codegen area",
+            ));
+        }
+
+        unsafe { write_code(target, &jump(replacement as u32)) };
+
+        Ok(Self {
+            target,
+            original,
+            slot,
+            trampoline: address as *const (),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Address of the trampoline which behaves like the unhooked function.
+    ///
+    /// Transmute it into the function type of the hooked symbol to call it, e.g. from within the replacement.
+    #[inline]
+    pub fn original(&self) -> *const () {
+        self.trampoline
+    }
+}
+
+impl Drop for Hook<'_> {
+    fn drop(&mut self) {
+        unsafe { write_code(self.target, &self.original) };
+        free_slot(self.slot);
+    }
+}
+
+unsafe impl Send for Hook<'_> {}
+unsafe impl Sync for Hook<'_> {}
+
+/// `b`/`bc` without the absolute address bit depend on the address of the instruction.
+fn is_relative_branch(instruction: u32) -> bool {
+    let opcode = instruction >> 26;
+    (opcode == 16 || opcode == 18) && instruction & 0b10 == 0
+}
+
+/// `lis r12, hi; ori r12, r12, lo; mtctr r12; bctr`
+///
+/// Only clobbers registers which are volatile at function entry.
+fn jump(address: u32) -> [u32; PATCH_LEN] {
+    [
+        0x3D80_0000 | address >> 16,
+        0x618C_0000 | address & 0xFFFF,
+        0x7D89_03A6,
+        0x4E80_0420,
+    ]
+}
+
+unsafe fn write_code(target: *mut u32, code: &[u32; PATCH_LEN]) {
+    let size = core::mem::size_of_val(code) as u32;
+    unsafe {
+        core::ptr::copy_nonoverlapping(code.as_ptr(), target, PATCH_LEN);
+        sys::DCFlushRange(target as *mut _, size);
+        sys::ICInvalidateRange(target as *mut _, size);
+    }
+}
+
+/// Find a free trampoline slot. Returns its index and address.
+fn allocate_slot() -> Result<(u32, u32), DynamicLoadingError> {
+    let (mut base, mut size) = (0, 0);
+    unsafe {
+        sys::OSGetCodegenVirtAddrRange(&mut base, &mut size);
+    }
+
+    let mut slots = SLOTS
+        .lock()
+        .map_err(|_| DynamicLoadingError::Unhookable("trampoline slots are poisoned"))?;
+    let slot = (0..size / SLOT_SIZE)
+        .find(|slot| !slots.contains(slot))
+        .ok_or(DynamicLoadingError::Unhookable("codegen area is full"))?;
+    slots.insert(slot);
+
+    // from the end, so the start of the area stays available to the application
+    Ok((slot, base + size - (slot + 1) * SLOT_SIZE))
+}
+
+fn free_slot(slot: u32) {
+    if let Ok(mut slots) = SLOTS.lock() {
+        slots.remove(&slot);
+    }
+}
@@ -26,6 +26,9 @@ use thiserror::Error;
 use wut_sys as sys;
 
 pub mod allocators;
+mod hook;
+
+pub use hook::Hook;
 
 #[derive(Debug, Error)]
 pub enum DynamicLoadingError {
@@ -55,6 +58,8 @@ pub enum DynamicLoadingError {
     SymbolNotFound,
     #[error("Pointer returned by module is null")]
     SymbolNullPointer,
+    #[error("Function cannot be hooked: {0}")]
+    Unhookable(&'static str),
     #[error("Unknown error: {0:#x?}")]
     Unknown(u32),
 }
@@ -163,6 +168,95 @@ impl Module {
     pub fn data<'lib, T>(&self, name: &str) -> Result<Symbol<'lib, T>, DynamicLoadingError> {
         self.get(name, sys::OSDynLoad_ExportType::OS_DYNLOAD_EXPORT_DATA)
     }
+
+    /// Get the address of a data symbol exported by the module.
+    ///
+    /// Unlike [Module::data], this resolves the extra level of indirection: the returned pointer points directly at the exported value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let module = Module::new("???.rpl")?;
+    /// let ptr = module.data_ptr::<u32>("???")?;
+    /// let value = unsafe { *ptr };
+    /// ```
+    pub fn data_ptr<T>(&self, name: &str) -> Result<*const T, DynamicLoadingError> {
+        let symbol = self.data::<()>(name)?;
+        Ok(unsafe { symbol.into_raw() } as *const T)
+    }
+
+    /// Read a copy of a data symbol exported by the module.
+    ///
+    /// # Safety
+    ///
+    /// The exported data must be a valid, properly aligned `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let module = Module::new("???.rpl")?;
+    /// let version = unsafe { module.read_data::<u32>("???")? };
+    /// ```
+    pub unsafe fn read_data<T: Copy>(&self, name: &str) -> Result<T, DynamicLoadingError> {
+        let ptr = self.data_ptr::<T>(name)?;
+        Ok(unsafe { ptr.read() })
+    }
+
+    /// Look up which of `names` are exported by the module.
+    ///
+    /// CafeOS does not expose the export table of a loaded RPL to applications, so exports cannot be listed directly. Instead every candidate name (e.g. taken from the `.fexports`/`.dexports` sections of the RPL file or a symbol list) is probed as a function and as a data export. Names which are not exported are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let module = Module::new("coreinit.rpl")?;
+    /// for export in module.exports(["OSGetTitleID", "OSReport", "NotAnExport"]) {
+    ///     println!("{} {:?} @ {:#010x}", export.name, export.kind, export.addr);
+    /// }
+    /// ```
+    pub fn exports<'a>(&self, names: impl IntoIterator<Item = &'a str>) -> Vec<Export> {
+        names
+            .into_iter()
+            .filter_map(|name| {
+                [ExportKind::Function, ExportKind::Data]
+                    .into_iter()
+                    .find_map(|kind| {
+                        self.get::<()>(name, kind.into())
+                            .ok()
+                            .map(|symbol| (kind, unsafe { symbol.into_raw() }))
+                    })
+                    .map(|(kind, pointer)| Export {
+                        name: String::from(name),
+                        kind,
+                        addr: pointer as u32,
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Kind of an exported symbol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportKind {
+    Function,
+    Data,
+}
+
+impl Into<sys::OSDynLoad_ExportType::Type> for ExportKind {
+    fn into(self) -> sys::OSDynLoad_ExportType::Type {
+        match self {
+            ExportKind::Function => sys::OSDynLoad_ExportType::OS_DYNLOAD_EXPORT_FUNC,
+            ExportKind::Data => sys::OSDynLoad_ExportType::OS_DYNLOAD_EXPORT_DATA,
+        }
+    }
+}
+
+/// An exported symbol of a [Module]. See [Module::exports].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Export {
+    pub name: String,
+    pub kind: ExportKind,
+    pub addr: u32,
 }
 
 impl Drop for Module {
@@ -299,14 +393,39 @@ impl Debug for RplInfo {
     }
 }
 
+/// Get info about `count` loaded RPLs, starting at `index`.
+///
+/// Returns an empty vector if the info cannot be retrieved.
 pub fn rpls_info(index: u32, count: usize) -> Vec<RplInfo> {
-    let mut info = Vec::with_capacity(count);
+    let mut info = Vec::<sys::OSDynLoad_NotifyData>::with_capacity(count);
 
-    unsafe { sys::OSDynLoad_GetRPLInfo(index, count as u32, info.as_mut_ptr()) };
+    let success = unsafe { sys::OSDynLoad_GetRPLInfo(index, count as u32, info.as_mut_ptr()) };
+    if success != 0 {
+        // SAFETY: the call succeeded, so all `count` entries were written
+        unsafe { info.set_len(count) };
+    }
 
     info.into_iter().map(RplInfo).collect()
 }
 
+/// Iterate over all currently loaded RPLs.
+///
+/// Like [loaded_rpls], this yields nothing on release versions of CafeOS.
+///
+/// # Example
+///
+/// ```
+/// use wut::dynload;
+///
+/// // the enumeration is only available on debug versions of CafeOS
+/// if dynload::loaded_rpls() > 0 {
+///     assert!(dynload::iter_rpls().any(|rpl| rpl.name().ends_with("coreinit.rpl")));
+/// }
+/// ```
+pub fn iter_rpls() -> impl Iterator<Item = RplInfo> {
+    rpls_info(0, loaded_rpls()).into_iter()
+}
+
 pub struct RplCallback<F>
 where
     F: 'static + Fn(Module, NotifyReason, RplInfo) + Send,
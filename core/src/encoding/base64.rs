@@ -0,0 +1,156 @@
+//! Base64 encoding as specified in [RFC 4648](https://www.rfc-editor.org/rfc/rfc4648).
+//!
+//! # Example
+//!
+//! ```
+//! use wut::encoding::base64::{self, Alphabet};
+//!
+//! assert_eq!(base64::encode(b"foobar"), "Zm9vYmFy");
+//! assert_eq!(base64::encode(b"fo"), "Zm8=");
+//! assert_eq!(base64::encode_with(&[0xfb, 0xff], Alphabet::UrlSafe, false), "-_8");
+//!
+//! assert_eq!(base64::decode("Zm8=")?, b"fo");
+//! assert_eq!(base64::decode("-_8")?, [0xfb, 0xff]);
+//! # Ok::<(), base64::DecodeError>(())
+//! ```
+//!
+//! Test vectors of RFC 4648, section 10:
+//!
+//! ```
+//! use wut::encoding::base64::{self, DecodeError};
+//!
+//! let vectors: [(&[u8], &str); 7] = [
+//!     (b"", ""),
+//!     (b"f", "Zg=="),
+//!     (b"fo", "Zm8="),
+//!     (b"foo", "Zm9v"),
+//!     (b"foob", "Zm9vYg=="),
+//!     (b"fooba", "Zm9vYmE="),
+//!     (b"foobar", "Zm9vYmFy"),
+//! ];
+//!
+//! for (data, text) in vectors {
+//!     assert_eq!(base64::encode(data), text);
+//!     assert_eq!(base64::decode(text)?, data);
+//! }
+//!
+//! assert_eq!(base64::decode("Zg="), Err(DecodeError::InvalidPadding));
+//! assert_eq!(base64::decode("Z"), Err(DecodeError::InvalidLength));
+//! assert_eq!(base64::decode("Zg==Zg=="), Err(DecodeError::InvalidPadding));
+//! # Ok::<(), DecodeError>(())
+//! ```
+
+use alloc::{string::String, vec::Vec};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DecodeError {
+    #[error("Invalid character {character:?} at index {index}")]
+    InvalidCharacter { character: char, index: usize },
+    #[error("Invalid length of encoded data")]
+    InvalidLength,
+    #[error("Invalid padding")]
+    InvalidPadding,
+}
+
+/// Characters used to encode the values 62 and 63.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Alphabet {
+    /// `+` and `/`
+    #[default]
+    Standard,
+    /// `-` and `_`, safe to use in URLs and file names.
+    UrlSafe,
+}
+
+impl Alphabet {
+    fn symbols(self) -> &'static [u8; 64] {
+        match self {
+            Alphabet::Standard => {
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/"
+            }
+            Alphabet::UrlSafe => {
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_"
+            }
+        }
+    }
+}
+
+/// Encode `data` with the [standard alphabet][Alphabet::Standard] and padding.
+pub fn encode(data: &[u8]) -> String {
+    encode_with(data, Alphabet::Standard, true)
+}
+
+/// Encode `data` with the given alphabet. With `padding`, the output is padded with `=` to a multiple of four characters.
+pub fn encode_with(data: &[u8], alphabet: Alphabet, padding: bool) -> String {
+    let symbols = alphabet.symbols();
+    let mut text = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, &byte)| {
+            group | (byte as u32) << (16 - 8 * i)
+        });
+
+        // a chunk of n bytes is encoded in n + 1 characters
+        for i in 0..=chunk.len() {
+            let index = (group >> (18 - 6 * i)) & 0x3f;
+            text.push(symbols[index as usize] as char);
+        }
+        if padding {
+            for _ in chunk.len()..3 {
+                text.push('=');
+            }
+        }
+    }
+
+    text
+}
+
+/// Decode `text`, which may use either [alphabet][Alphabet] and may or may not be padded.
+///
+/// # Errors
+///
+/// Returns a [DecodeError] if `text` contains characters outside of the alphabets, padding in the wrong place or has a length which no encoded data can have.
+pub fn decode(text: &str) -> Result<Vec<u8>, DecodeError> {
+    let bytes = text.as_bytes();
+
+    let data = bytes
+        .strip_suffix(b"==")
+        .or_else(|| bytes.strip_suffix(b"="))
+        .unwrap_or(bytes);
+    if data.len() != bytes.len() && bytes.len() % 4 != 0 {
+        return Err(DecodeError::InvalidPadding);
+    }
+    if data.len() % 4 == 1 {
+        return Err(DecodeError::InvalidLength);
+    }
+
+    let mut output = Vec::with_capacity(data.len() * 3 / 4);
+
+    for (chunk_index, chunk) in data.chunks(4).enumerate() {
+        let mut group = 0u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            let value = match c {
+                b'A'..=b'Z' => c - b'A',
+                b'a'..=b'z' => c - b'a' + 26,
+                b'0'..=b'9' => c - b'0' + 52,
+                b'+' | b'-' => 62,
+                b'/' | b'_' => 63,
+                b'=' => return Err(DecodeError::InvalidPadding),
+                _ => {
+                    let index = chunk_index * 4 + i;
+                    return Err(DecodeError::InvalidCharacter {
+                        character: text[index..].chars().next().unwrap_or_default(),
+                        index,
+                    });
+                }
+            };
+            group |= (value as u32) << (18 - 6 * i);
+        }
+
+        // n characters encode n - 1 bytes
+        output.extend_from_slice(&group.to_be_bytes()[1..chunk.len()]);
+    }
+
+    Ok(output)
+}
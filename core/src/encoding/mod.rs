@@ -0,0 +1,3 @@
+//! Encodings of binary data as text.
+
+pub mod base64;
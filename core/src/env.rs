@@ -5,11 +5,36 @@
 use crate::{
     fs,
     path::{Path, PathBuf},
-    sync::{LazyLock, Mutex},
+    sync::{LazyLock, Mutex, OnceLock},
+    title,
 };
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+use core::ffi;
+use thiserror::Error;
 
 static CWD: LazyLock<Mutex<PathBuf>> = LazyLock::new(|| Mutex::new(PathBuf::from("/")));
 
+/// Initialized with all `KEY=VALUE` launch arguments.
+static VARS: LazyLock<Mutex<BTreeMap<String, String>>> = LazyLock::new(|| {
+    Mutex::new(
+        args()
+            .skip(1)
+            .filter_map(|arg| {
+                let (key, value) = arg.split_once('=')?;
+                (!key.is_empty() && !key.starts_with('-')).then(|| (key.into(), value.into()))
+            })
+            .collect(),
+    )
+});
+
+static ARGS: OnceLock<Vec<String>> = OnceLock::new();
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum VarError {
+    #[error("environment variable not found")]
+    NotPresent,
+}
+
 /// Constants associated with the current target
 pub mod consts {
     /// A string describing the architecture of the CPU that is currently in use. An example value may be: `"x86"`, `"arm"` or `"riscv64"`.
@@ -60,3 +85,164 @@ pub fn set_current_dir<P: AsRef<Path>>(path: P) -> Result<(), fs::FilesystemErro
     *CWD.lock().unwrap() = path.as_ref().to_path_buf();
     Ok(())
 }
+
+/// Returns the full path of the running executable.
+///
+/// If the first launch argument is the path of an `.rpx`, as passed by the Homebrew Launcher, it is used. Otherwise, the executable of the running title is looked up in `/vol/code/cos.xml`.
+///
+/// # Errors
+///
+/// Returns an [`FilesystemError`][fs::FilesystemError] if `cos.xml` cannot be read or does not name an executable.
+///
+/// # Example
+///
+/// ```
+/// // e.g. "/vol/external01/wiiu/apps/example/example.rpx"
+/// let exe = wut::env::current_exe()?;
+/// let config = exe.with_extension("ini");
+/// ```
+pub fn current_exe() -> Result<PathBuf, fs::FilesystemError> {
+    if let Some(path) = arg(0).and_then(|arg| exe_path(&arg)) {
+        return Ok(path);
+    }
+
+    let cos = fs::read_to_string("/vol/code/cos.xml")?;
+    let name = xml_value(&cos, "argstr")
+        .filter(|name| !name.is_empty())
+        .ok_or(fs::FilesystemError::NotFound)?;
+
+    Ok(PathBuf::from("/vol/code").join(name))
+}
+
+/// Convert a launch argument into an absolute path if it names an executable.
+fn exe_path(arg: &str) -> Option<PathBuf> {
+    if !arg.to_ascii_lowercase().ends_with(consts::EXE_SUFFIX) {
+        return None;
+    }
+
+    let path = if let Some(rest) = arg.strip_prefix("sd:") {
+        format!("/vol/external01{rest}")
+    } else if let Some(rest) = arg.strip_prefix("fs:") {
+        String::from(rest)
+    } else {
+        String::from(arg)
+    };
+
+    path.starts_with('/').then(|| PathBuf::from(path))
+}
+
+/// Content of the first `<tag ...>value</tag>` element.
+fn xml_value<'a>(xml: &'a str, tag: &str) -> Option<&'a str> {
+    let start = xml.find(&format!("<{tag}"))?;
+    let rest = &xml[start..];
+    let rest = &rest[rest.find('>')? + 1..];
+    let end = rest.find(&format!("</{tag}>"))?;
+    Some(rest[..end].trim())
+}
+
+/// Returns a directory for temporary files.
+///
+/// This is the value of the `TMPDIR` environment variable if set, otherwise a directory for the current title on the SD card (`/vol/external01/wiiu/tmp/<title id>`). The directory is created if it does not exist yet, but its content is never removed automatically.
+///
+/// # Example
+///
+/// ```
+/// let path = wut::env::temp_dir().join("download.part");
+/// wut::fs::write(&path, b"...")?;
+/// ```
+pub fn temp_dir() -> PathBuf {
+    let path = match var("TMPDIR") {
+        Ok(dir) => PathBuf::from(dir),
+        Err(_) => PathBuf::from(format!(
+            "/vol/external01/wiiu/tmp/{:016x}",
+            title::current_title()
+        )),
+    };
+
+    // failing here should not prevent the caller from reporting a more specific error later
+    let _ = fs::create_dir_all(&path);
+
+    path
+}
+
+/// Fetches the environment variable `key` from the current process.
+///
+/// Environment variables only exist within the application and are not shared with other processes.
+///
+/// # Example
+///
+/// ```
+/// use wut::env;
+///
+/// env::set_var("LOG_LEVEL", "debug");
+/// assert_eq!(env::var("LOG_LEVEL"), Ok("debug".into()));
+/// ```
+pub fn var<K: AsRef<str>>(key: K) -> Result<String, VarError> {
+    VARS.lock()
+        .unwrap()
+        .get(key.as_ref())
+        .cloned()
+        .ok_or(VarError::NotPresent)
+}
+
+/// Sets the environment variable `key` to `value`, replacing any previous value.
+pub fn set_var<K: Into<String>, V: Into<String>>(key: K, value: V) {
+    VARS.lock().unwrap().insert(key.into(), value.into());
+}
+
+/// Removes the environment variable `key`.
+pub fn remove_var<K: AsRef<str>>(key: K) {
+    VARS.lock().unwrap().remove(key.as_ref());
+}
+
+/// Returns a snapshot of all environment variables, sorted by key.
+pub fn vars() -> impl Iterator<Item = (String, String)> {
+    VARS.lock()
+        .unwrap()
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+/// Store the arguments passed to the application's entry point.
+///
+/// Called by [process::init_with_args][crate::process::init_with_args]. Only the first call has an effect.
+///
+/// # Safety
+///
+/// `argv` must point to `argc` valid, null terminated C strings.
+pub(crate) unsafe fn init_args(argc: ffi::c_int, argv: *const *const ffi::c_char) {
+    let mut args = Vec::new();
+
+    if !argv.is_null() {
+        for i in 0..argc.max(0) as usize {
+            let arg = unsafe { *argv.add(i) };
+            if !arg.is_null() {
+                args.push(unsafe { ffi::CStr::from_ptr(arg) }.to_string_lossy().into_owned());
+            }
+        }
+    }
+
+    let _ = ARGS.set(args);
+}
+
+/// Returns the arguments the application was launched with.
+///
+/// The first element is typically the path of the executable, e.g. when launched from the Homebrew Launcher. Arguments are only available if the application was started via `#[wut::main]` or [process::init_with_args][crate::process::init_with_args].
+///
+/// # Example
+///
+/// ```
+/// for arg in wut::env::args().skip(1) {
+///     println!("{arg}");
+/// }
+/// ```
+pub fn args() -> impl Iterator<Item = String> {
+    ARGS.get().cloned().unwrap_or_default().into_iter()
+}
+
+/// Returns the `n`-th launch argument. See [args].
+pub fn arg(n: usize) -> Option<String> {
+    ARGS.get()?.get(n).cloned()
+}
@@ -0,0 +1,47 @@
+//! Helpers for interacting with the system libraries.
+//!
+//! Most of Nintendo's `nn` libraries, like the software keyboard or the error viewer, use null terminated UTF-16 strings.
+
+use alloc::{string::String, vec::Vec};
+
+/// Encode `s` as a null terminated UTF-16 string.
+///
+/// # Example
+///
+/// ```
+/// use wut::ffi::into_utf16;
+///
+/// assert_eq!(into_utf16("Wii"), [0x57, 0x69, 0x69, 0]);
+/// ```
+pub fn into_utf16(s: &str) -> Vec<u16> {
+    let mut v: Vec<u16> = s.encode_utf16().collect();
+    v.push(0);
+    v
+}
+
+/// Decode a null terminated UTF-16 string. Invalid code units are replaced with `U+FFFD`.
+///
+/// Returns an empty string if `ptr` is null.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a valid, null terminated UTF-16 string.
+///
+/// # Example
+///
+/// ```
+/// use wut::ffi::{from_utf16, into_utf16};
+///
+/// let text = into_utf16("Hello");
+/// assert_eq!(unsafe { from_utf16(text.as_ptr()) }, "Hello");
+/// ```
+pub unsafe fn from_utf16(ptr: *const u16) -> String {
+    if ptr.is_null() {
+        return String::new();
+    }
+
+    let len = (0..).take_while(|&i| unsafe { *ptr.add(i) } != 0).count();
+    let v = unsafe { core::slice::from_raw_parts(ptr, len) };
+
+    String::from_utf16_lossy(v)
+}
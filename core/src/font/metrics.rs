@@ -0,0 +1,214 @@
+use crate::sync::LazyLock;
+use core::ptr;
+use wut_sys as sys;
+
+/// The standard system font, loaded from shared memory on first use.
+static FONT: LazyLock<Option<TrueType>> = LazyLock::new(|| {
+    let mut data = ptr::null_mut();
+    let mut size = 0;
+
+    if unsafe {
+        sys::OSGetSharedData(
+            sys::OSSharedDataType::OS_SHAREDDATATYPE_FONT_STANDARD,
+            0,
+            &mut data,
+            &mut size,
+        )
+    } == 0
+        || data.is_null()
+    {
+        return None;
+    }
+
+    // shared data is mapped for the lifetime of the process
+    let data = unsafe { core::slice::from_raw_parts(data as *const u8, size as usize) };
+    TrueType::parse(data)
+});
+
+/// Width and height in pixels of `text` rendered with the system font at a pixel height of `size`.
+///
+/// Every `\n` starts a new line. The width is the one of the widest line. Kerning is not taken into account, so the result may be off by a few pixels for some character pairs.
+///
+/// Returns `(0.0, 0.0)` if the system font is not available.
+///
+/// # Example
+///
+/// ```
+/// let (width, height) = wut::font::measure("Press \u{E000} to continue", 24.0);
+/// let x = (854.0 - width) / 2.0;
+/// ```
+pub fn measure(text: &str, size: f32) -> (f32, f32) {
+    let Some(font) = FONT.as_ref() else {
+        return (0.0, 0.0);
+    };
+    let scale = font.scale(size);
+
+    let (mut width, mut lines) = (0.0f32, 0);
+    for line in text.split('\n') {
+        let advance: u32 = line.chars().map(|c| font.advance(c) as u32).sum();
+        width = width.max(advance as f32 * scale);
+        lines += 1;
+    }
+
+    let height =
+        (lines - 1) as f32 * line_height(size) + (font.ascent - font.descent) as f32 * scale;
+    (width, height)
+}
+
+/// Distance in pixels between the baselines of two consecutive lines at a pixel height of `size`.
+///
+/// Returns `0.0` if the system font is not available.
+pub fn line_height(size: f32) -> f32 {
+    match FONT.as_ref() {
+        Some(font) => (font.ascent - font.descent + font.line_gap) as f32 * font.scale(size),
+        None => 0.0,
+    }
+}
+
+/// Horizontal metrics of a TrueType font.
+struct TrueType {
+    data: &'static [u8],
+    ascent: i32,
+    descent: i32,
+    line_gap: i32,
+    /// Offset of the `hmtx` table.
+    hmtx: usize,
+    /// Number of entries with an advance width in `hmtx`.
+    metrics: u16,
+    /// Offset of the used `cmap` subtable.
+    cmap: usize,
+}
+
+impl TrueType {
+    fn parse(data: &'static [u8]) -> Option<Self> {
+        let hhea = table(data, b"hhea")?;
+        let hmtx = table(data, b"hmtx")?;
+
+        let mut s = Self {
+            data,
+            ascent: read_u16(data, hhea + 4)? as i16 as i32,
+            descent: read_u16(data, hhea + 6)? as i16 as i32,
+            line_gap: read_u16(data, hhea + 8)? as i16 as i32,
+            hmtx,
+            metrics: read_u16(data, hhea + 34)?,
+            cmap: 0,
+        };
+        s.cmap = s.find_cmap()?;
+
+        Some(s)
+    }
+
+    /// Find a Unicode subtable of format 4 or 12, preferring full Unicode coverage.
+    fn find_cmap(&self) -> Option<usize> {
+        let cmap = table(self.data, b"cmap")?;
+        let count = read_u16(self.data, cmap + 2)? as usize;
+
+        let mut best = None;
+        for i in 0..count {
+            let record = cmap + 4 + i * 8;
+            let (platform, encoding) = (
+                read_u16(self.data, record)?,
+                read_u16(self.data, record + 2)?,
+            );
+            let offset = cmap + read_u32(self.data, record + 4)? as usize;
+
+            match (platform, encoding, read_u16(self.data, offset)?) {
+                (0, _, 12) | (3, 10, 12) => return Some(offset),
+                (0, _, 4) | (3, 1, 4) => best = Some(offset),
+                _ => (),
+            }
+        }
+
+        best
+    }
+
+    /// Scale factor from font units to pixels for a pixel height of `size`.
+    fn scale(&self, size: f32) -> f32 {
+        size / (self.ascent - self.descent) as f32
+    }
+
+    /// Advance width of `c` in font units.
+    fn advance(&self, c: char) -> u16 {
+        let glyph = self.glyph(c).unwrap_or(0) as usize;
+        let index = glyph.min(self.metrics.saturating_sub(1) as usize);
+        read_u16(self.data, self.hmtx + index * 4).unwrap_or(0)
+    }
+
+    fn glyph(&self, c: char) -> Option<u16> {
+        let data = self.data;
+        let table = self.cmap;
+        let c = c as u32;
+
+        match read_u16(data, table)? {
+            4 => {
+                if c > 0xFFFF {
+                    return None;
+                }
+                let segments = read_u16(data, table + 6)? as usize / 2;
+                let end_codes = table + 14;
+                let start_codes = end_codes + segments * 2 + 2;
+                let deltas = start_codes + segments * 2;
+                let range_offsets = deltas + segments * 2;
+
+                for i in 0..segments {
+                    if c > read_u16(data, end_codes + i * 2)? as u32 {
+                        continue;
+                    }
+                    let start = read_u16(data, start_codes + i * 2)? as u32;
+                    if c < start {
+                        return None;
+                    }
+
+                    let delta = read_u16(data, deltas + i * 2)?;
+                    let range_offset = read_u16(data, range_offsets + i * 2)? as usize;
+                    if range_offset == 0 {
+                        return Some((c as u16).wrapping_add(delta));
+                    }
+
+                    let address = range_offsets + i * 2 + range_offset + (c - start) as usize * 2;
+                    return match read_u16(data, address)? {
+                        0 => None,
+                        glyph => Some(glyph.wrapping_add(delta)),
+                    };
+                }
+                None
+            }
+            12 => {
+                let groups = read_u32(data, table + 12)? as usize;
+                for i in 0..groups {
+                    let group = table + 16 + i * 12;
+                    let (start, end) = (read_u32(data, group)?, read_u32(data, group + 4)?);
+                    if (start..=end).contains(&c) {
+                        return Some((read_u32(data, group + 8)? + c - start) as u16);
+                    }
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Offset of the table with `tag`.
+fn table(data: &[u8], tag: &[u8; 4]) -> Option<usize> {
+    let count = read_u16(data, 4)? as usize;
+    (0..count)
+        .map(|i| 12 + i * 16)
+        .find(|&record| data.get(record..record + 4) == Some(tag))
+        .and_then(|record| read_u32(data, record + 8))
+        .map(|offset| offset as usize)
+}
+
+#[inline]
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+#[inline]
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
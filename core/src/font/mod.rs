@@ -16,6 +16,16 @@
 //! # Note
 //!
 //! The actual appearance of these symbols depends on the system font and may not render correctly on all platforms.
+//!
+//! [Icon] allows enumerating all icons and looking them up by name at runtime.
+//!
+//! # Metrics
+//!
+//! [measure] and [line_height] compute the size of text rendered with the system font, e.g. to center or right-align it.
+
+mod metrics;
+
+pub use metrics::{line_height, measure};
 
 pub mod icons {
     pub const BTN_A: char = '\u{E000}';
@@ -151,3 +161,176 @@ pub mod icons {
 
     pub const SPACE: char = '\u{3000}';
 }
+
+macro_rules! icon_enum {
+    ($($variant:ident => $name:ident,)*) => {
+        /// Runtime representation of the constants in [icons].
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use wut::font::Icon;
+        ///
+        /// for icon in Icon::all() {
+        ///     println!("{} {}", icon.name(), icon.char());
+        /// }
+        ///
+        /// assert_eq!(Icon::from_name("BTN_A"), Some(Icon::BtnA));
+        /// ```
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Icon {
+            $($variant,)*
+        }
+
+        impl Icon {
+            const ALL: &'static [Icon] = &[$(Icon::$variant,)*];
+
+            /// All icons in declaration order.
+            ///
+            /// Some icons are aliases and share their character with another icon, e.g. [Icon::WiimoteBtnUp] and [Icon::BtnUp].
+            #[inline]
+            pub fn all() -> &'static [Icon] {
+                Self::ALL
+            }
+
+            /// The character of the icon in the system font.
+            pub const fn char(self) -> char {
+                match self {
+                    $(Self::$variant => icons::$name,)*
+                }
+            }
+
+            /// Name of the corresponding constant in [icons], e.g. `"BTN_A"`.
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $(Self::$variant => stringify!($name),)*
+                }
+            }
+
+            /// Look up an icon by the name of its constant in [icons]. See [Icon::name].
+            pub fn from_name(name: &str) -> Option<Icon> {
+                match name {
+                    $(stringify!($name) => Some(Self::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl From<Icon> for char {
+            fn from(value: Icon) -> Self {
+                value.char()
+            }
+        }
+
+        impl core::fmt::Display for Icon {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Write::write_char(f, self.char())
+            }
+        }
+    };
+}
+
+icon_enum! {
+    BtnA => BTN_A,
+    BtnB => BTN_B,
+    BtnX => BTN_X,
+    BtnY => BTN_Y,
+    BtnL => BTN_L,
+    BtnR => BTN_R,
+    BtnDpad => BTN_DPAD,
+    Target => TARGET,
+    CaptureStill => CAPTURE_STILL,
+    CaptureVideo => CAPTURE_VIDEO,
+    Spinner0 => SPINNER_0,
+    Spinner1 => SPINNER_1,
+    Spinner2 => SPINNER_2,
+    Spinner3 => SPINNER_3,
+    Spinner4 => SPINNER_4,
+    Spinner5 => SPINNER_5,
+    Spinner6 => SPINNER_6,
+    Spinner7 => SPINNER_7,
+    BtnUp => BTN_UP,
+    BtnDown => BTN_DOWN,
+    BtnLeft => BTN_LEFT,
+    BtnRight => BTN_RIGHT,
+    BtnUpDown => BTN_UP_DOWN,
+    BtnDownUp => BTN_DOWN_UP,
+    BtnLeftRight => BTN_LEFT_RIGHT,
+    BtnRightLeft => BTN_RIGHT_LEFT,
+    WiimoteBtnPower => WIIMOTE_BTN_POWER,
+    WiimoteBtnDpad => WIIMOTE_BTN_DPAD,
+    WiimoteBtnA => WIIMOTE_BTN_A,
+    WiimoteBtnB => WIIMOTE_BTN_B,
+    WiimoteBtnHome => WIIMOTE_BTN_HOME,
+    WiimoteBtnPlus => WIIMOTE_BTN_PLUS,
+    WiimoteBtnMinus => WIIMOTE_BTN_MINUS,
+    WiimoteBtn1 => WIIMOTE_BTN_1,
+    WiimoteBtn2 => WIIMOTE_BTN_2,
+    WiimoteBtnUp => WIIMOTE_BTN_UP,
+    WiimoteBtnDown => WIIMOTE_BTN_DOWN,
+    WiimoteBtnLeft => WIIMOTE_BTN_LEFT,
+    WiimoteBtnRight => WIIMOTE_BTN_RIGHT,
+    NunchukStick => NUNCHUK_STICK,
+    NunchukBtnC => NUNCHUK_BTN_C,
+    NunchukBtnZ => NUNCHUK_BTN_Z,
+    ClassicBtnDpad => CLASSIC_BTN_DPAD,
+    ClassicBtnHome => CLASSIC_BTN_HOME,
+    ClassicBtnPlus => CLASSIC_BTN_PLUS,
+    ClassicBtnMinus => CLASSIC_BTN_MINUS,
+    ClassicBtnA => CLASSIC_BTN_A,
+    ClassicBtnB => CLASSIC_BTN_B,
+    ClassicBtnX => CLASSIC_BTN_X,
+    ClassicBtnY => CLASSIC_BTN_Y,
+    ClassicStickL => CLASSIC_STICK_L,
+    ClassicStickR => CLASSIC_STICK_R,
+    ClassicBtnL => CLASSIC_BTN_L,
+    ClassicBtnR => CLASSIC_BTN_R,
+    ClassicBtnZl => CLASSIC_BTN_ZL,
+    ClassicBtnZr => CLASSIC_BTN_ZR,
+    ClassicBtnUp => CLASSIC_BTN_UP,
+    ClassicBtnDown => CLASSIC_BTN_DOWN,
+    ClassicBtnLeft => CLASSIC_BTN_LEFT,
+    ClassicBtnRight => CLASSIC_BTN_RIGHT,
+    KbdReturn => KBD_RETURN,
+    KbdSpace => KBD_SPACE,
+    HandPoint => HAND_POINT,
+    HandPoint1 => HAND_POINT_1,
+    HandPoint2 => HAND_POINT_2,
+    HandPoint3 => HAND_POINT_3,
+    HandPoint4 => HAND_POINT_4,
+    HandFist => HAND_FIST,
+    HandFist1 => HAND_FIST_1,
+    HandFist2 => HAND_FIST_2,
+    HandFist3 => HAND_FIST_3,
+    HandFist4 => HAND_FIST_4,
+    HandOpen => HAND_OPEN,
+    HandOpen1 => HAND_OPEN_1,
+    HandOpen2 => HAND_OPEN_2,
+    HandOpen3 => HAND_OPEN_3,
+    HandOpen4 => HAND_OPEN_4,
+    Wii => WII,
+    Help => HELP,
+    Close => CLOSE,
+    CloseAlt => CLOSE_ALT,
+    Back => BACK,
+    Home => HOME,
+    Gamepad => GAMEPAD,
+    Wiimote => WIIMOTE,
+    Circlepad => CIRCLEPAD,
+    BtnPower => BTN_POWER,
+    Steps => STEPS,
+    Playcoin => PLAYCOIN,
+    BtnTv => BTN_TV,
+    ArrowLeftRight => ARROW_LEFT_RIGHT,
+    ArrowUpDown => ARROW_UP_DOWN,
+    ArrowCw => ARROW_CW,
+    ArrowCcw => ARROW_CCW,
+    ArrowRight => ARROW_RIGHT,
+    ArrowLeft => ARROW_LEFT,
+    ArrowUp => ARROW_UP,
+    ArrowDown => ARROW_DOWN,
+    ArrowUpRight => ARROW_UP_RIGHT,
+    X => X,
+    Nfc => NFC,
+    Space => SPACE,
+}
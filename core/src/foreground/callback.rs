@@ -0,0 +1,192 @@
+use crate::sync::{LazyLock, Mutex};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{
+    ffi, ptr,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use wut_sys as sys;
+
+/// Shared, so a snapshot of the registry can be called without holding its lock.
+type Callback = Arc<Mutex<Box<dyn Fn() + Send>>>;
+type Callbacks = LazyLock<Mutex<Vec<Callback>>>;
+
+static ACQUIRE: Callbacks = LazyLock::new(|| Mutex::new(Vec::new()));
+static RELEASE: Callbacks = LazyLock::new(|| Mutex::new(Vec::new()));
+static RECLAIM: LazyLock<Mutex<Vec<Reclaimer>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+static REGISTERED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone)]
+struct Reclaimer {
+    name: &'static str,
+    order: u32,
+    on_foreground_lost: Callback,
+    on_foreground_gained: Callback,
+}
+
+/// Registry of subsystems which have to release memory while the application is in the background.
+///
+/// MEM1 and the foreground bucket are taken away from the application when it leaves the foreground. Subsystems which allocate from them register a pair of callbacks, which are run in a fixed order:
+///
+/// * When the foreground is gained, in ascending `order`, before any [on_acquire] callbacks.
+/// * When the foreground is lost, in descending `order`, after all [on_release] callbacks.
+///
+/// This way, subsystems which depend on each other reallocate their memory in the same order every time, and user callbacks can always use them.
+///
+/// # Example
+///
+/// ```
+/// use wut::foreground::MemoryReclaim;
+///
+/// MemoryReclaim::register(
+///     "my_game::audio",
+///     MemoryReclaim::USER,
+///     || free_audio_buffers(),
+///     || alloc_audio_buffers(),
+/// );
+/// ```
+pub struct MemoryReclaim;
+
+impl MemoryReclaim {
+    /// OSScreen framebuffers of [screen][crate::screen].
+    pub const SCREEN: u32 = 100;
+    /// GPU buffers of [gx2][crate::gx2].
+    pub const GRAPHICS: u32 = 200;
+    /// Suggested order for application subsystems, which may depend on all built-in ones.
+    pub const USER: u32 = 1000;
+
+    /// Register the callbacks of the subsystem `name`.
+    ///
+    /// Registering a `name` again replaces its previous callbacks. Subsystems with the same `order` are run sorted by name. Changes made from within a callback take effect the next time the foreground changes.
+    pub fn register<L, G>(
+        name: &'static str,
+        order: u32,
+        on_foreground_lost: L,
+        on_foreground_gained: G,
+    ) where
+        L: Fn() + Send + 'static,
+        G: Fn() + Send + 'static,
+    {
+        register();
+
+        let Ok(mut reclaimers) = RECLAIM.lock() else {
+            return;
+        };
+        reclaimers.retain(|r| r.name != name);
+        reclaimers.push(Reclaimer {
+            name,
+            order,
+            on_foreground_lost: callback(on_foreground_lost),
+            on_foreground_gained: callback(on_foreground_gained),
+        });
+        reclaimers.sort_by_key(|r| (r.order, r.name));
+    }
+
+    /// Remove the callbacks of the subsystem `name`.
+    pub fn unregister(name: &str) {
+        if let Ok(mut reclaimers) = RECLAIM.lock() {
+            reclaimers.retain(|r| r.name != name);
+        }
+    }
+}
+
+/// Register a function which is called every time the application moves back into the foreground.
+///
+/// Use this to resume audio, reallocate resources or continue a paused simulation. Callbacks registered from within a callback are first called the next time the foreground changes.
+///
+/// # Example
+///
+/// ```
+/// wut::foreground::on_acquire(|| println!("welcome back"));
+/// wut::foreground::on_release(|| println!("see you later"));
+/// ```
+pub fn on_acquire<F: Fn() + Send + 'static>(f: F) {
+    register();
+    if let Ok(mut callbacks) = ACQUIRE.lock() {
+        callbacks.push(callback(f));
+    }
+}
+
+/// Register a function which is called every time the application is moved into the background, e.g. when the Home Menu is opened.
+///
+/// The system may suspend the application shortly after, so use this to save state or pause audio. See [on_acquire].
+pub fn on_release<F: Fn() + Send + 'static>(f: F) {
+    register();
+    if let Ok(mut callbacks) = RELEASE.lock() {
+        callbacks.push(callback(f));
+    }
+}
+
+/// Check if the application currently is in the foreground.
+#[inline]
+pub fn is_foreground() -> bool {
+    unsafe { sys::ProcUIInForeground() != 0 }
+}
+
+/// Register the ProcUI callbacks once.
+fn register() {
+    if REGISTERED.swap(true, Ordering::AcqRel) {
+        return;
+    }
+
+    use sys::ProcUICallbackType as T;
+    unsafe {
+        sys::ProcUIRegisterCallback(
+            T::PROCUI_CALLBACK_ACQUIRE,
+            Some(_acquire),
+            ptr::null_mut(),
+            100,
+        );
+        sys::ProcUIRegisterCallback(
+            T::PROCUI_CALLBACK_RELEASE,
+            Some(_release),
+            ptr::null_mut(),
+            100,
+        );
+    }
+}
+
+unsafe extern "C" fn _acquire(_: *mut ffi::c_void) -> u32 {
+    for r in reclaimers() {
+        call(&r.on_foreground_gained);
+    }
+    run(&ACQUIRE);
+    0
+}
+
+unsafe extern "C" fn _release(_: *mut ffi::c_void) -> u32 {
+    run(&RELEASE);
+    for r in reclaimers().iter().rev() {
+        call(&r.on_foreground_lost);
+    }
+    0
+}
+
+fn callback<F: Fn() + Send + 'static>(f: F) -> Callback {
+    Arc::new(Mutex::new(Box::new(f)))
+}
+
+fn call(f: &Callback) {
+    if let Ok(f) = f.lock() {
+        f();
+    }
+}
+
+/// Snapshot of the registered reclaimers. The lock is not held while they run, so they may change the registry.
+fn reclaimers() -> Vec<Reclaimer> {
+    RECLAIM
+        .lock()
+        .map(|reclaimers| reclaimers.to_vec())
+        .unwrap_or_default()
+}
+
+/// Call a snapshot of `callbacks`. The lock is not held while they run, so they may register further callbacks.
+fn run(callbacks: &Callbacks) {
+    let snapshot = match callbacks.lock() {
+        Ok(callbacks) => callbacks.to_vec(),
+        Err(_) => return,
+    };
+
+    for f in snapshot.iter() {
+        call(f);
+    }
+}
@@ -13,10 +13,15 @@
 //! // Open the Controller Sync overlay
 //! wut::foreground::controller_sync();
 //! ```
+//!
+//! Applications can react to being moved out of and back into the foreground with [on_release] and [on_acquire]. Subsystems which hold memory that is only available in the foreground register with [MemoryReclaim].
 
 use wut_sys as c_wut;
 
 pub mod browser;
+mod callback;
+
+pub use callback::{MemoryReclaim, is_foreground, on_acquire, on_release};
 
 /// Opens the Home Menu (`Home`-Button).
 #[inline]
@@ -0,0 +1,180 @@
+use crate::{
+    fs::{FilesystemError, FsHandler},
+    path::{Path, PathBuf},
+    rrc::{Rrc, RrcGuard},
+};
+use alloc::{ffi::CString, string::String, vec::Vec};
+use wut_sys as sys;
+
+static FSA: Rrc = Rrc::new(
+    || unsafe {
+        sys::FSAInit();
+    },
+    || unsafe {
+        sys::FSAShutdown();
+    },
+);
+
+/// Mount points which are probed by [devices], in the order they are reported.
+const MOUNT_POINTS: [(&str, &str, DeviceKind); 6] = [
+    ("sd", "/vol/external01", DeviceKind::Sd),
+    ("usb01", "/vol/storage_usb01", DeviceKind::Usb),
+    ("usb02", "/vol/storage_usb02", DeviceKind::Usb),
+    ("mlc", "/vol/storage_mlc01", DeviceKind::Mlc),
+    ("content", "/vol/content", DeviceKind::Content),
+    ("save", "/vol/save", DeviceKind::Save),
+];
+
+/// Type of storage behind a [Device].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceKind {
+    /// SD card.
+    Sd,
+    /// External USB storage.
+    Usb,
+    /// Internal storage (MLC).
+    Mlc,
+    /// Content of the running title.
+    Content,
+    /// Save data of the running title.
+    Save,
+}
+
+impl DeviceKind {
+    /// Whether the volume is organized in quotas which have to be committed with `FSFlushQuota`. FAT volumes like the SD card are not.
+    pub(crate) fn has_quota(&self) -> bool {
+        matches!(self, Self::Usb | Self::Mlc | Self::Save)
+    }
+}
+
+/// A mounted filesystem volume. See [devices].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Device {
+    /// Short name, e.g. `sd` or `usb01`.
+    pub name: String,
+    pub mount_path: PathBuf,
+    pub kind: DeviceKind,
+    /// Free space in bytes.
+    pub free_space: u64,
+    /// Size of the underlying device in bytes. `0` if it cannot be determined.
+    pub total_space: u64,
+}
+
+/// List all mounted filesystem volumes.
+///
+/// Volumes which are not mounted or not accessible to the running title are left out.
+///
+/// # Example
+///
+/// ```
+/// use wut::fs;
+///
+/// for device in fs::devices() {
+///     println!("{}: {} MiB free", device.mount_path, device.free_space >> 20);
+/// }
+/// ```
+pub fn devices() -> Vec<Device> {
+    let Ok(fs) = FsHandler::new() else {
+        return Vec::new();
+    };
+    let fsa = FsaHandler::new();
+
+    MOUNT_POINTS
+        .iter()
+        .filter_map(|&(name, mount_path, kind)| {
+            let path = CString::new(mount_path).unwrap();
+            let free_space = free_space(&fs, &path).ok()?;
+            let total_space = fsa
+                .as_ref()
+                .and_then(|fsa| fsa.device_size(&path))
+                .unwrap_or(0);
+
+            Some(Device {
+                name: String::from(name),
+                mount_path: PathBuf::from(mount_path),
+                kind,
+                free_space,
+                total_space,
+            })
+        })
+        .collect()
+}
+
+/// Mounted volume which contains `path`.
+///
+/// Relative paths are resolved against the current working directory. Returns `None` if `path` is not on any of the [devices].
+///
+/// # Example
+///
+/// ```
+/// use wut::fs;
+///
+/// let device = fs::device_for("/vol/external01/wiiu/apps")?;
+/// assert_eq!(device.kind, fs::DeviceKind::Sd);
+/// ```
+pub fn device_for<P: AsRef<Path>>(path: P) -> Option<Device> {
+    let path = path.as_ref().absolute().ok()?;
+    let (_, mount_path, _) = mount_point(&path)?;
+
+    devices()
+        .into_iter()
+        .find(|device| device.mount_path.as_str() == mount_path)
+}
+
+/// Name, mount path and kind of the volume which contains the absolute `path`, without accessing the filesystem.
+pub(crate) fn mount_point(path: &Path) -> Option<(&'static str, &'static str, DeviceKind)> {
+    MOUNT_POINTS
+        .iter()
+        .filter(|(_, mount_path, _)| path.starts_with(mount_path))
+        .max_by_key(|(_, mount_path, _)| mount_path.len())
+        .copied()
+}
+
+fn free_space(fs: &FsHandler, path: &CString) -> Result<u64, FilesystemError> {
+    let mut size = 0;
+
+    let status = unsafe {
+        sys::FSGetFreeSpaceSize(
+            &mut *fs.client.borrow_mut(),
+            &mut *fs.block.borrow_mut(),
+            path.as_ptr(),
+            &mut size,
+            fs.error_mask,
+        )
+    };
+    FilesystemError::try_from(status)?;
+
+    Ok(size)
+}
+
+/// Client of the lower level FSA interface, which is required to query device information.
+struct FsaHandler {
+    client: sys::FSAClientHandle,
+    _resource: RrcGuard,
+}
+
+impl FsaHandler {
+    fn new() -> Option<Self> {
+        let _resource = FSA.acquire();
+        let client = unsafe { sys::FSAAddClient(core::ptr::null_mut()) };
+
+        (client >= 0).then_some(Self { client, _resource })
+    }
+
+    fn device_size(&self, path: &CString) -> Option<u64> {
+        let mut info = sys::FSADeviceInfo::default();
+
+        let status = unsafe { sys::FSAGetDeviceInfo(self.client, path.as_ptr(), &mut info) };
+
+        (status == sys::FSError::FS_ERROR_OK)
+            .then(|| info.deviceSizeInSectors * info.deviceSectorSize as u64)
+    }
+}
+
+impl Drop for FsaHandler {
+    fn drop(&mut self) {
+        unsafe {
+            sys::FSADelClient(self.client);
+        }
+    }
+}
@@ -2,11 +2,16 @@
 //!
 //! This module contains basic methods to manipulate the contents of the local filesystem.
 
+mod device;
+mod read_async;
 mod walkdir;
-pub use walkdir::walkdir;
+pub use device::{Device, DeviceKind, device_for, devices};
+pub use read_async::{ReadHandle, read_async};
+pub use walkdir::{WalkDir, WalkDirEntry, WalkDirIter, walkdir};
 
 use crate::{
     path::{Path, PathBuf},
+    ptr::DmaBuffer,
     rrc::{Rrc, RrcGuard},
     time::DateTime,
 };
@@ -48,6 +53,8 @@ pub enum FilesystemError {
     NotAFile,
     #[error("Invalid permissions for action on object")]
     InvalidPermissions,
+    #[error("File size exceeds the 4 GiB limit of the filesystem")]
+    TooLarge,
     #[error("Invalid combination of requested file mode")]
     InvalidModeCombination {
         read: bool,
@@ -319,6 +326,14 @@ impl Metadata {
         Ok(DateTime::from(cal))
     }
 
+    /// Last access time of the entry.
+    ///
+    /// The filesystem does not record access times, so this is the same as [modified][Metadata::modified].
+    #[inline]
+    pub fn accessed(&self) -> Result<DateTime, FilesystemError> {
+        self.modified()
+    }
+
     #[inline]
     pub fn file_type(&self) -> FileType {
         FileType(FlagSet::<MetadataFlags>::new_truncated(self.0.flags))
@@ -339,6 +354,12 @@ impl Metadata {
         self.file_type().is_symlink()
     }
 
+    /// Check if the entry is encrypted and cannot be opened, like some vWii files.
+    #[inline]
+    pub fn is_encrypted(&self) -> bool {
+        self.file_type().0.contains(MetadataFlags::Encrypted)
+    }
+
     #[inline]
     pub fn len(&self) -> u64 {
         self.0.size as u64
@@ -379,11 +400,57 @@ impl From<sys::FSStat> for Metadata {
 /// ## Valid combination
 /// - read
 /// - create
+/// - write (file must exist, content is overwritten in place)
 /// - read, write
+/// - (read), write, create (content is overwritten in place if the file exists)
 /// - (write), append, create
 /// - write, create, truncate
 /// - read, write, create, truncate
 /// - read, (write), append, create
+///
+/// ## Note
+///
+/// `fopen` has no mode which creates a missing file without truncating an existing one. For `write` + `create` without `truncate`, the mode is therefore chosen by checking whether the file exists right before opening it. These are two separate filesystem calls, so if another thread or process creates or removes the file in between, it may be truncated or fail to open.
+///
+/// # Example
+///
+/// ```
+/// use wut::fs::{File, OpenOptions};
+///
+/// // overwrite the first bytes of an existing file without truncating it
+/// let mut file = OpenOptions::new().write(true).open("save.bin")?;
+/// file.write_all(&[1, 2, 3])?;
+///
+/// // read and write an existing file
+/// let mut file = OpenOptions::new().read(true).write(true).open("save.bin")?;
+/// ```
+///
+/// Existing content is kept unless `truncate` is set:
+///
+/// ```
+/// use wut::fs::{self, OpenOptions};
+///
+/// let path = "/vol/external01/open_options.bin";
+/// fs::write(path, [0, 0, 0, 0])?;
+///
+/// // write only
+/// let mut file = OpenOptions::new().write(true).open(path)?;
+/// file.write_all(&[1, 2])?;
+/// drop(file);
+/// assert_eq!(fs::read(path)?, [1, 2, 0, 0]);
+///
+/// // read and write, created if missing
+/// let mut file = OpenOptions::new().read(true).write(true).create(true).open(path)?;
+/// let mut content = Vec::new();
+/// file.read_to_end(&mut content)?;
+/// assert_eq!(content, [1, 2, 0, 0]);
+/// file.rewind()?;
+/// file.write_all(&[3])?;
+/// drop(file);
+/// assert_eq!(fs::read(path)?, [3, 2, 0, 0]);
+///
+/// fs::remove(path)?;
+/// ```
 pub struct OpenOptions {
     read: bool,
     write: bool,
@@ -461,7 +528,7 @@ impl OpenOptions {
     pub fn open<P: AsRef<Path>>(&self, path: P) -> Result<File, FilesystemError> {
         let fs = FsHandler::new()?;
         let str = CString::new(path.as_ref().as_str())?;
-        let mode = self.file_mode()?;
+        let mode = self.file_mode(path.as_ref())?;
         let mut handle = sys::FSFileHandle::default();
 
         crate::println!("mode: {:?}", mode);
@@ -485,7 +552,8 @@ impl OpenOptions {
         })
     }
 
-    fn file_mode(&self) -> Result<&ffi::CStr, FilesystemError> {
+    /// `fopen` mode for the options. `path` is only accessed if the mode depends on whether the file already exists.
+    fn file_mode(&self, path: &Path) -> Result<&ffi::CStr, FilesystemError> {
         match (
             self.read,
             self.write,
@@ -497,7 +565,17 @@ impl OpenOptions {
             (true, false, false, false, false) => Ok(c"r"),
             (false, true, false, true, true) | (false, false, false, true, false) => Ok(c"w"),
             (false, _, true, true, false) => Ok(c"a"),
-            (true, true, _, false, false) => Ok(c"r+"),
+            (true, true, _, false, false) | (false, true, false, false, false) => Ok(c"r+"),
+            // open or create without truncating; racy, see the note on `OpenOptions`
+            (read, true, false, true, false) => {
+                if exists(path).unwrap_or(false) {
+                    Ok(c"r+")
+                } else if read {
+                    Ok(c"w+")
+                } else {
+                    Ok(c"w")
+                }
+            }
             (true, true, _, true, true) => Ok(c"w+"),
             (true, _, true, true, false) => Ok(c"a+"),
             (read, write, append, create, truncate) => {
@@ -530,45 +608,6 @@ pub struct File {
     path: PathBuf,
 }
 
-// region: FsBuffer
-
-struct FsBuffer {
-    data: *mut u8,
-    len: u32,
-}
-
-impl FsBuffer {
-    fn new<T: Into<u32>>(len: T) -> Self {
-        let len = len.into();
-        Self {
-            data: unsafe { sys::MEMAllocFromDefaultHeapEx.unwrap()(len, 0x40) } as *mut _,
-            len,
-        }
-    }
-
-    fn from(data: &[u8]) -> Self {
-        let b = Self::new(data.len() as u32);
-        unsafe {
-            core::ptr::copy(data.as_ptr(), b.data, b.len as usize);
-        }
-        b
-    }
-
-    fn as_slice(&self) -> &[u8] {
-        unsafe { alloc::slice::from_raw_parts(self.data as *const _, self.len as usize) }
-    }
-}
-
-impl Drop for FsBuffer {
-    fn drop(&mut self) {
-        unsafe {
-            sys::MEMFreeToDefaultHeap.unwrap()(self.data as *mut _);
-        }
-    }
-}
-
-// endregion
-
 impl File {
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, FilesystemError> {
         OpenOptions::new().read(true).open(path)
@@ -614,14 +653,14 @@ impl File {
         let total_len = self.metadata().unwrap().len();
         let current_pos = self.seek_position()?;
 
-        let buffer = FsBuffer::new(total_len as u32 - current_pos);
+        let mut buffer = DmaBuffer::new((total_len as u32 - current_pos) as usize);
         let read = unsafe {
             sys::FSReadFile(
                 &mut *self.fs.client.borrow_mut(),
                 &mut *self.fs.block.borrow_mut(),
-                buffer.data,
+                buffer.as_mut_ptr(),
                 1,
-                buffer.len,
+                buffer.len() as u32,
                 self.handle,
                 0,
                 self.fs.error_mask,
@@ -633,8 +672,74 @@ impl File {
         Ok(read as usize)
     }
 
+    /// Pull some bytes from this source into `buf`, advancing the seek position.
+    ///
+    /// Returns the number of bytes read, which is `0` once the end of the file is reached.
+    pub fn read(&mut self, buf: &mut [u8]) -> Result<usize, FilesystemError> {
+        let mut buffer = DmaBuffer::new(buf.len());
+        let read = unsafe {
+            sys::FSReadFile(
+                &mut *self.fs.client.borrow_mut(),
+                &mut *self.fs.block.borrow_mut(),
+                buffer.as_mut_ptr(),
+                1,
+                buffer.len() as u32,
+                self.handle,
+                0,
+                self.fs.error_mask,
+            )
+        };
+        FilesystemError::try_from(read)?;
+
+        let read = read as usize;
+        buf[..read].copy_from_slice(&buffer[..read]);
+        Ok(read)
+    }
+
+    /// Reads `buf.len()` bytes starting at `offset` from the start of the file.
+    ///
+    /// Returns the number of bytes read, which is only less than `buf.len()` if the end of the file is reached. The seek position is the same as before the call.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wut::fs::File;
+    ///
+    /// let mut archive = File::open("/vol/content/data.arc")?;
+    /// let mut entry = [0; 16];
+    /// archive.read_at(0x40, &mut entry)?;
+    /// ```
+    pub fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> Result<usize, FilesystemError> {
+        let offset = u32::try_from(offset).map_err(|_| FilesystemError::TooLarge)?;
+        let position = self.seek_position()?;
+
+        let mut buffer = DmaBuffer::new(buf.len());
+        let read = unsafe {
+            sys::FSReadFileWithPos(
+                &mut *self.fs.client.borrow_mut(),
+                &mut *self.fs.block.borrow_mut(),
+                buffer.as_mut_ptr(),
+                1,
+                buffer.len() as u32,
+                offset,
+                self.handle,
+                0,
+                self.fs.error_mask,
+            )
+        };
+        // restore the position even if the read failed
+        self.seek(SeekFrom::Start(position))?;
+        FilesystemError::try_from(read)?;
+
+        let read = read as usize;
+        buf[..read].copy_from_slice(&buffer[..read]);
+        Ok(read)
+    }
+
     /// Reads all bytes from seeker until EOF in this source, converting them into a String, and appending them onto `buf`.
     ///
+    /// Invalid UTF-8 is replaced with `U+FFFD`. Use [read_to_string_strict][File::read_to_string_strict] to detect it instead.
+    ///
     /// If successful, this function will return the total number of bytes read. The seeker will be moved by the length of `buf` backwards?
     ///
     /// # Errors
@@ -647,6 +752,18 @@ impl File {
         Ok(size)
     }
 
+    /// Like [read_to_string][File::read_to_string], but fails with [InvalidCharacters][FilesystemError::InvalidCharacters] instead of replacing invalid UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// If any read error is encountered or the content is not valid UTF-8, this function immediately returns. No bytes will be written to `buf`.
+    pub fn read_to_string_strict(&mut self, buf: &mut String) -> Result<usize, FilesystemError> {
+        let mut buffer = Vec::new();
+        let size = self.read_to_end(&mut buffer)?;
+        buf.push_str(core::str::from_utf8(&buffer)?);
+        Ok(size)
+    }
+
     /// Writes all bytes from seeker onwards in this source.
     ///
     /// If successful, this function will return the total number of bytes written. The seeker will be moved by the length of `buf` backwards?
@@ -657,14 +774,14 @@ impl File {
     pub fn write_all(&mut self, buf: &[u8]) -> Result<u32, FilesystemError> {
         crate::println!("{}", buf.len());
 
-        let buffer = FsBuffer::from(buf);
+        let mut buffer = DmaBuffer::from_slice(buf);
         let written = unsafe {
             sys::FSWriteFile(
                 &mut *self.fs.client.borrow_mut(),
                 &mut *self.fs.block.borrow_mut(),
-                buffer.data,
+                buffer.as_mut_ptr(),
                 1,
-                buffer.len,
+                buffer.len() as u32,
                 self.handle,
                 0,
                 self.fs.error_mask,
@@ -753,6 +870,35 @@ impl File {
         Ok(())
     }
 
+    /// Truncates or extends the file to `size` bytes.
+    ///
+    /// If the file is shrunk, the remaining data is kept. If it is extended, the new bytes are filled with zeros.
+    ///
+    /// Like in `std`, the seek position is unaffected. The only exception is a position beyond the new end of the file, which is clamped to the end, because the filesystem cannot seek past the end of a file.
+    pub fn set_len(&mut self, size: u64) -> Result<(), FilesystemError> {
+        let size = u32::try_from(size).map_err(|_| FilesystemError::TooLarge)?;
+        let len = self.metadata()?.len() as u32;
+        let position = self.seek_position()?;
+
+        if size < len {
+            self.seek(SeekFrom::Start(size))?;
+            self.truncate()?;
+        } else if size > len {
+            self.seek(SeekFrom::End(0))?;
+
+            let zeros = alloc::vec![0; COPY_CHUNK_SIZE.min((size - len) as usize)];
+            let mut remaining = (size - len) as usize;
+            while remaining > 0 {
+                let chunk = remaining.min(zeros.len());
+                self.write_all(&zeros[..chunk])?;
+                remaining -= chunk;
+            }
+        }
+
+        self.seek(SeekFrom::Start(position.min(size)))?;
+        Ok(())
+    }
+
     pub fn flush(&mut self) -> Result<(), FilesystemError> {
         let status = unsafe {
             sys::FSFlushFile(
@@ -765,6 +911,52 @@ impl File {
         FilesystemError::try_from(status)?;
         Ok(())
     }
+
+    /// Writes the content of the file to the storage device.
+    ///
+    /// Unlike [sync_all][File::sync_all], metadata like the directory entry may still only be cached, so the file can appear with its old size or not at all after a power loss.
+    pub fn sync_data(&mut self) -> Result<(), FilesystemError> {
+        self.flush()
+    }
+
+    /// Writes the content and metadata of the file to the storage device.
+    ///
+    /// [flush][File::flush] only hands the data over to the filesystem, which may keep it cached. On quota-backed volumes (save data, internal and Wii U formatted USB storage), this additionally commits the quota of the [device][device_for] containing the file, so all changes in it are guaranteed to be on the device once this returns. FAT volumes like the SD card have no quotas, so flushing the file is all that is needed there. Use this for data which has to survive the SD card being removed or the console being turned off, e.g. save files.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [NotFound][FilesystemError::NotFound] if the file is not on one of the known [devices], as it cannot be determined how to commit it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wut::fs::File;
+    ///
+    /// let mut file = File::create("/vol/save/common/progress.bin")?;
+    /// file.write_all(&progress)?;
+    /// file.sync_all()?;
+    /// ```
+    pub fn sync_all(&mut self) -> Result<(), FilesystemError> {
+        self.sync_data()?;
+
+        let path = self.path.absolute()?;
+        let (_, mount_path, kind) = device::mount_point(&path).ok_or(FilesystemError::NotFound)?;
+        if !kind.has_quota() {
+            return Ok(());
+        }
+        let str = CString::new(mount_path)?;
+
+        let status = unsafe {
+            sys::FSFlushQuota(
+                &mut *self.fs.client.borrow_mut(),
+                &mut *self.fs.block.borrow_mut(),
+                str.as_ptr(),
+                self.fs.error_mask,
+            )
+        };
+        FilesystemError::try_from(status)?;
+        Ok(())
+    }
 }
 
 impl Drop for File {
@@ -883,8 +1075,91 @@ impl fmt::Debug for DirEntry {
 std functions
 */
 
-pub fn copy<P: AsRef<Path>, Q: AsRef<Path>>(_from: P, _to: Q) -> Result<u64, FilesystemError> {
-    todo!()
+/// Size of the chunks in which [copy] and [File::set_len] write data, so large files never have to fit into memory.
+const COPY_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Copies the contents of one file to another, overwriting the destination if it exists.
+///
+/// Returns the number of bytes copied.
+pub fn copy<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> Result<u64, FilesystemError> {
+    let mut source = File::open(from)?;
+    let mut target = File::create(to)?;
+
+    let mut buffer = alloc::vec![0; COPY_CHUNK_SIZE];
+    let mut total = 0;
+    loop {
+        let read = source.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        target.write_all(&buffer[..read])?;
+        total += read as u64;
+    }
+
+    Ok(total)
+}
+
+/// Recursively copies the directory `from` with all its content to `to`.
+///
+/// Missing directories are created and existing files in `to` are overwritten, so an interrupted copy can simply be repeated. Returns the total number of bytes copied. Fails with [NotFound][FilesystemError::NotFound] if `from` is not a directory and with the first error encountered while reading the tree, so a successful return means nothing was skipped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use wut::fs;
+///
+/// // back up save data
+/// let bytes = fs::copy_dir_all("/vol/save/common", "/vol/external01/backup/common")?;
+/// ```
+///
+/// Nested directories are copied as well:
+///
+/// ```
+/// use wut::fs;
+///
+/// fs::create_dir_all("/vol/external01/from/a/b")?;
+/// fs::write("/vol/external01/from/root.txt", "root")?;
+/// fs::write("/vol/external01/from/a/one.txt", "one")?;
+/// fs::write("/vol/external01/from/a/b/two.txt", "two")?;
+///
+/// let bytes = fs::copy_dir_all("/vol/external01/from", "/vol/external01/to")?;
+///
+/// assert_eq!(bytes, 10);
+/// assert_eq!(fs::read_to_string("/vol/external01/to/root.txt")?, "root");
+/// assert_eq!(fs::read_to_string("/vol/external01/to/a/one.txt")?, "one");
+/// assert_eq!(fs::read_to_string("/vol/external01/to/a/b/two.txt")?, "two");
+///
+/// fs::remove_dir_all("/vol/external01/from")?;
+/// fs::remove_dir_all("/vol/external01/to")?;
+/// ```
+pub fn copy_dir_all<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> Result<u64, FilesystemError> {
+    let (from, to) = (from.as_ref(), to.as_ref());
+    if !metadata(from)?.is_dir() {
+        return Err(FilesystemError::NotFound);
+    }
+
+    create_dir_all(to)?;
+
+    let mut total = 0;
+    for entry in WalkDir::new(from) {
+        let entry = entry?;
+        let source = entry.path();
+        let Ok(relative) = source.strip_prefix(from) else {
+            continue;
+        };
+        let target = to.join(relative);
+
+        if entry.file_type().is_dir() {
+            create_dir_all(&target)?;
+        } else {
+            if let Some(parent) = target.parent() {
+                create_dir_all(parent)?;
+            }
+            total += copy(&source, &target)?;
+        }
+    }
+
+    Ok(total)
 }
 
 pub fn create_dir<P: AsRef<Path>>(path: P) -> Result<(), FilesystemError> {
@@ -993,6 +1268,26 @@ pub fn read_to_string<P: AsRef<Path>>(path: P) -> Result<String, FilesystemError
     Ok(content)
 }
 
+/// Like [read_to_string], but fails with [InvalidCharacters][FilesystemError::InvalidCharacters] if the file is not valid UTF-8.
+///
+/// # Example
+///
+/// ```
+/// use wut::fs::{self, FilesystemError};
+///
+/// match fs::read_to_string_strict("/vol/external01/config.ini") {
+///     Ok(config) => parse(&config),
+///     Err(FilesystemError::InvalidCharacters(e)) => println!("config is corrupted: {e}"),
+///     Err(e) => return Err(e),
+/// }
+/// ```
+pub fn read_to_string_strict<P: AsRef<Path>>(path: P) -> Result<String, FilesystemError> {
+    let mut file = File::open(path)?;
+    let mut content = String::new();
+    file.read_to_string_strict(&mut content)?;
+    Ok(content)
+}
+
 pub fn remove<P: AsRef<Path>>(path: P) -> Result<(), FilesystemError> {
     let fs = FsHandler::new()?;
     let str = CString::new(path.as_ref().as_str())?;
@@ -1071,3 +1366,53 @@ pub fn write<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> Result<(),
     let _ = file.write_all(&contents)?;
     Ok(())
 }
+
+/// Writes `contents` to a file, replacing its previous contents without ever leaving a partially written file behind.
+///
+/// The data is first written to a sibling temporary file (`path` with `.tmp` appended) and committed to the device with [File::sync_all]. Only then it is renamed to `path`. If writing the temporary file fails, it is removed and `path` is untouched.
+///
+/// # Note
+///
+/// The replace is not atomic. CafeOS filesystems refuse to rename onto an existing file, so an existing `path` is removed right before the rename. If power is lost or the rename fails in between, `path` is missing, but the complete new data remains in the temporary file. It is never removed once `path` is gone, so check for it if `path` cannot be found.
+///
+/// # Examples
+///
+/// ```no_run
+/// use wut::fs;
+///
+/// fs::write_atomic("/vol/save/common/slot0.bin", &[1, 2, 3])?;
+/// ```
+pub fn write_atomic<P: AsRef<Path>, C: AsRef<[u8]>>(
+    path: P,
+    contents: C,
+) -> Result<(), FilesystemError> {
+    let path = path.as_ref();
+    let tmp = PathBuf::from(alloc::format!("{}.tmp", path.as_str()));
+
+    let written = (|| {
+        let mut file = File::create(&tmp)?;
+        file.write_all(contents.as_ref())?;
+        file.sync_all()
+    })();
+
+    if let Err(e) = written {
+        let _ = remove(&tmp);
+        return Err(e);
+    }
+
+    match rename(&tmp, path) {
+        Err(FilesystemError::AlreadyExists) => (),
+        Err(e) => {
+            let _ = remove(&tmp);
+            return Err(e);
+        }
+        Ok(()) => return Ok(()),
+    }
+
+    if let Err(e) = remove(path) {
+        let _ = remove(&tmp);
+        return Err(e);
+    }
+    // from here on, the temporary file holds the only copy of the data
+    rename(&tmp, path)
+}
@@ -0,0 +1,90 @@
+use crate::{
+    fs::{self, FilesystemError},
+    path::Path,
+    sync::mpsc::{self, Receiver},
+    thread::{self, JoinHandle},
+};
+use alloc::vec::Vec;
+
+type ReadResult = Result<Vec<u8>, FilesystemError>;
+
+/// Read the entire content of a file on a background thread.
+///
+/// Returns immediately. The result is retrieved via the returned [ReadHandle]. If no worker thread can be spawned, the file is read on the calling thread instead, so the result is available right away.
+///
+/// # Example
+///
+/// ```
+/// use wut::fs;
+///
+/// let mut level = Some(fs::read_async("/vol/content/level2.bin"));
+///
+/// while wut::process::running() {
+///     if let Some(result) = level.as_ref().and_then(|handle| handle.poll()) {
+///         let data = result?;
+///         level = None;
+///         // ...
+///     }
+///
+///     // render the loading screen
+/// }
+/// ```
+pub fn read_async<P: AsRef<Path>>(path: P) -> ReadHandle {
+    let path = path.as_ref().to_path_buf();
+    let (sender, receiver) = mpsc::sync_channel(1);
+
+    let worker = {
+        let sender = sender.clone();
+        let path = path.clone();
+        thread::Builder::default()
+            .name("fs::read_async")
+            .spawn(move || {
+                // the handle may already be dropped, in which case the result is discarded
+                let _ = sender.send(fs::read(path));
+            })
+    };
+
+    let worker = match worker {
+        Ok(worker) => Some(worker),
+        Err(_) => {
+            let _ = sender.send(fs::read(path));
+            None
+        }
+    };
+
+    ReadHandle {
+        receiver,
+        _worker: worker,
+    }
+}
+
+/// Handle to a file read started by [read_async].
+///
+/// Dropping the handle does not cancel the read. The worker thread finishes in the background, discards the data and cleans up after itself.
+#[must_use = "the file content can only be retrieved via the handle"]
+pub struct ReadHandle {
+    receiver: Receiver<ReadResult>,
+    /// Detached on drop.
+    _worker: Option<JoinHandle<()>>,
+}
+
+impl ReadHandle {
+    /// Get the result without blocking.
+    ///
+    /// Returns `None` while the read is in progress. The result is only returned once, all later calls return `None`.
+    pub fn poll(&self) -> Option<ReadResult> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Block until the read is finished and return the result.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the read, or [FilesystemError::AllRead] if the result was already taken by [poll][ReadHandle::poll].
+    pub fn join(self) -> ReadResult {
+        // the channel is only disconnected once the result was received
+        self.receiver
+            .recv()
+            .unwrap_or(Err(FilesystemError::AllRead))
+    }
+}
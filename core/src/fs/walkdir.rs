@@ -1,33 +1,227 @@
 use crate::{
-    fs,
+    fs::{self, DirEntry, FilesystemError},
     path::{Path, PathBuf},
 };
-use alloc::vec;
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
+use core::{cmp::Ordering, ops::Deref};
 
+/// Recursively list all files and directories below `path`.
+///
+/// Shorthand for [WalkDir] without any limits. Entries which cannot be read are skipped.
 pub fn walkdir<P: AsRef<Path>>(path: P) -> Vec<PathBuf> {
-    let mut result = Vec::new();
-    let path = path.as_ref();
-
-    // Check if the provided path exists and is a directory
-    if path.is_dir() {
-        // Use a stack for iterative recursion
-        let mut stack = vec![path.to_path_buf()];
-
-        while let Some(current) = stack.pop() {
-            if let Ok(entries) = fs::read_dir(&current) {
-                for entry in entries.flatten() {
-                    let entry_path = entry.path();
-                    result.push(entry_path.clone());
-
-                    // If the entry is a directory, add it to the stack
-                    if entry_path.is_dir() {
-                        stack.push(entry_path);
+    WalkDir::new(path)
+        .into_iter()
+        .flatten()
+        .map(|entry| entry.path())
+        .collect()
+}
+
+type Sorter = Box<dyn FnMut(&DirEntry, &DirEntry) -> Ordering>;
+type Filter = Box<dyn FnMut(&WalkDirEntry) -> bool>;
+
+/// Builder for a recursive directory traversal.
+///
+/// The root itself is not yielded. Its direct children have a depth of 1, their children a depth of 2 and so on. Directories are yielded before their content.
+///
+/// # Example
+///
+/// ```
+/// use wut::fs::WalkDir;
+///
+/// // like `fs::read_dir`, but sorted by name
+/// for entry in WalkDir::new("/vol/external01")
+///     .max_depth(1)
+///     .sort_by(|a, b| a.file_name().cmp(&b.file_name()))
+/// {
+///     println!("{}", entry?.path());
+/// }
+///
+/// // all `.rpx` files up to three levels deep, skipping hidden directories
+/// let apps = WalkDir::new("/vol/external01/wiiu")
+///     .max_depth(3)
+///     .filter_entry(|e| !e.file_name().starts_with('.'))
+///     .into_iter()
+///     .flatten()
+///     .filter(|e| e.file_name().ends_with(".rpx"));
+/// ```
+pub struct WalkDir {
+    root: PathBuf,
+    min_depth: usize,
+    max_depth: usize,
+    follow_links: bool,
+    sorter: Option<Sorter>,
+    filter: Option<Filter>,
+}
+
+impl WalkDir {
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            min_depth: 1,
+            max_depth: usize::MAX,
+            follow_links: false,
+            sorter: None,
+            filter: None,
+        }
+    }
+
+    /// Only yield entries with a depth of at least `depth`. Shallower directories are still traversed.
+    pub fn min_depth(mut self, depth: usize) -> Self {
+        self.min_depth = depth;
+        self
+    }
+
+    /// Do not descend deeper than `depth`. A `max_depth` of 1 only yields the direct children of the root, just like [read_dir][fs::read_dir].
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Descend into directories which are links. Disabled by default.
+    pub fn follow_links(mut self, follow: bool) -> Self {
+        self.follow_links = follow;
+        self
+    }
+
+    /// Sort the entries of each directory with `cmp` before yielding them.
+    pub fn sort_by<F>(mut self, cmp: F) -> Self
+    where
+        F: FnMut(&DirEntry, &DirEntry) -> Ordering + 'static,
+    {
+        self.sorter = Some(Box::new(cmp));
+        self
+    }
+
+    /// Skip entries for which `predicate` returns `false`. Skipped directories are not descended into.
+    pub fn filter_entry<F>(mut self, predicate: F) -> Self
+    where
+        F: FnMut(&WalkDirEntry) -> bool + 'static,
+    {
+        self.filter = Some(Box::new(predicate));
+        self
+    }
+}
+
+impl IntoIterator for WalkDir {
+    type Item = Result<WalkDirEntry, FilesystemError>;
+    type IntoIter = WalkDirIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let mut iter = WalkDirIter {
+            stack: Vec::new(),
+            pending_error: None,
+            options: self,
+        };
+
+        if iter.options.max_depth > 0 {
+            let root = iter.options.root.clone();
+            iter.push_dir(&root, 1);
+        }
+
+        iter
+    }
+}
+
+/// A [DirEntry] yielded by [WalkDir] together with its depth.
+pub struct WalkDirEntry {
+    entry: DirEntry,
+    depth: usize,
+}
+
+impl WalkDirEntry {
+    /// Depth relative to the root of the traversal. Direct children of the root have a depth of 1.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    #[inline]
+    pub fn into_dir_entry(self) -> DirEntry {
+        self.entry
+    }
+}
+
+impl Deref for WalkDirEntry {
+    type Target = DirEntry;
+
+    fn deref(&self) -> &Self::Target {
+        &self.entry
+    }
+}
+
+/// Iterator created by [WalkDir].
+pub struct WalkDirIter {
+    options: WalkDir,
+    /// Unvisited entries of each open directory in reverse order, together with their depth.
+    stack: Vec<(Vec<DirEntry>, usize)>,
+    pending_error: Option<FilesystemError>,
+}
+
+impl WalkDirIter {
+    fn push_dir(&mut self, path: &Path, depth: usize) {
+        let mut entries = Vec::new();
+
+        match fs::read_dir(path) {
+            Ok(dir) => {
+                for entry in dir {
+                    match entry {
+                        Ok(entry) => entries.push(entry),
+                        Err(e) => {
+                            self.pending_error = Some(e);
+                            break;
+                        }
                     }
                 }
             }
+            Err(e) => self.pending_error = Some(e),
         }
+
+        if let Some(sorter) = &mut self.options.sorter {
+            entries.sort_by(|a, b| sorter(a, b));
+        }
+        entries.reverse();
+
+        self.stack.push((entries, depth));
     }
+}
 
-    result
+impl Iterator for WalkDirIter {
+    type Item = Result<WalkDirEntry, FilesystemError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(e) = self.pending_error.take() {
+                return Some(Err(e));
+            }
+
+            let (entries, depth) = self.stack.last_mut()?;
+            let depth = *depth;
+            let Some(entry) = entries.pop() else {
+                self.stack.pop();
+                continue;
+            };
+
+            let entry = WalkDirEntry { entry, depth };
+
+            if let Some(filter) = &mut self.options.filter {
+                if !filter(&entry) {
+                    continue;
+                }
+            }
+
+            let file_type = entry.file_type();
+            if file_type.is_dir()
+                && depth < self.options.max_depth
+                && (self.options.follow_links || !file_type.is_symlink())
+            {
+                self.push_dir(&entry.path(), depth + 1);
+            }
+
+            if depth >= self.options.min_depth {
+                return Some(Ok(entry));
+            }
+        }
+    }
 }
+
+impl core::iter::FusedIterator for WalkDirIter {}
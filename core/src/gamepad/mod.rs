@@ -2,12 +2,24 @@
 //!
 //! This module simplifies gamepad input on the Wii U, offering a unified interface for various controller types. It focuses exclusively on **buttons and joysticks**, providing a common subset of input methods across different gamepads. This module does not offer full support for every controller's unique features.
 
+mod tracker;
+
+pub use tracker::{StateDelta, Tracker, clear_button_repeat, set_button_repeat};
+
 use crate::rrc::{Rrc, RrcGuard};
-use alloc::vec;
-use core::{fmt::Debug, u16};
+use alloc::{vec, vec::Vec};
+use core::{
+    fmt::{self, Debug},
+    sync::atomic::{AtomicU8, Ordering},
+    time::Duration,
+    u16,
+};
 use flagset::{FlagSet, flags};
 use thiserror::Error;
-use wut_math::FloatingMathExt;
+use wut_math::{
+    FloatingMathExt,
+    vec::{Vec2, Vec3},
+};
 use wut_sys as sys;
 
 pub(crate) static KPAD: Rrc = Rrc::new(
@@ -19,6 +31,9 @@ pub(crate) static KPAD: Rrc = Rrc::new(
     },
 );
 
+/// Charging state of the Pro Controller on each WPAD channel (one bit per channel), as reported by the last sample read by [Gamepad::poll].
+static PRO_CHARGING: AtomicU8 = AtomicU8::new(0);
+
 pub(crate) static VPAD: Rrc = Rrc::new(
     || unsafe {
         sys::VPADInit();
@@ -28,6 +43,11 @@ pub(crate) static VPAD: Rrc = Rrc::new(
     },
 );
 
+/// Steps per second at which the DRC motor plays back a rumble pattern.
+const VPAD_MOTOR_PATTERN_RATE: u128 = 120;
+/// Maximum length of a DRC rumble pattern in bits.
+const VPAD_MOTOR_PATTERN_MAX_BITS: u128 = 120;
+
 flags! {
     pub enum Button: u32 {
         A,
@@ -63,6 +83,28 @@ flags! {
     }
 }
 
+impl fmt::Display for Button {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Formats a set of [Button]s as their names joined with `+`, e.g. `A+ZR`. Parsed by [Button::parse_set].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ButtonNames(pub FlagSet<Button>);
+
+impl fmt::Display for ButtonNames {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, button) in self.0.into_iter().enumerate() {
+            if i > 0 {
+                f.write_str("+")?;
+            }
+            f.write_str(button.name())?;
+        }
+        Ok(())
+    }
+}
+
 impl Button {
     const VPAD_BUTTON_MAPPING: [(u32, Button); 26] = [
         (sys::VPADButtons::VPAD_BUTTON_A, Button::A),
@@ -291,6 +333,81 @@ impl Button {
         FlagSet::<Button>::default()
     }
 
+    /// Human-readable name of the button, e.g. `"ZR"` or `"LStickUp"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Button::A => "A",
+            Button::B => "B",
+            Button::X => "X",
+            Button::Y => "Y",
+            Button::Left => "Left",
+            Button::Right => "Right",
+            Button::Up => "Up",
+            Button::Down => "Down",
+            Button::L => "L",
+            Button::R => "R",
+            Button::ZL => "ZL",
+            Button::ZR => "ZR",
+            Button::Plus => "Plus",
+            Button::Minus => "Minus",
+            Button::Home => "Home",
+            Button::Sync => "Sync",
+            Button::RStick => "RStick",
+            Button::LStick => "LStick",
+            Button::RStickLeft => "RStickLeft",
+            Button::RStickRight => "RStickRight",
+            Button::RStickUp => "RStickUp",
+            Button::RStickDown => "RStickDown",
+            Button::LStickLeft => "LStickLeft",
+            Button::LStickRight => "LStickRight",
+            Button::LStickUp => "LStickUp",
+            Button::LStickDown => "LStickDown",
+            Button::One => "One",
+            Button::Two => "Two",
+            Button::Z => "Z",
+            Button::C => "C",
+        }
+    }
+
+    /// Button with the given [name][Button::name]. Names are compared case-insensitively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wut::gamepad::Button;
+    ///
+    /// assert_eq!(Button::from_name("zr"), Some(Button::ZR));
+    /// assert_eq!(Button::from_name("Start"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Button> {
+        FlagSet::<Button>::full()
+            .into_iter()
+            .find(|button| button.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Parse a combination of buttons written as names joined with `+`, as produced by [ButtonNames].
+    ///
+    /// An empty string is parsed as no buttons. Returns `None` if any name is unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wut::gamepad::{Button, ButtonNames};
+    ///
+    /// let buttons = Button::A | Button::ZR;
+    /// let text = ButtonNames(buttons).to_string();
+    /// assert_eq!(text, "A+ZR");
+    /// assert_eq!(Button::parse_set(&text), Some(buttons));
+    /// ```
+    pub fn parse_set(text: &str) -> Option<FlagSet<Button>> {
+        if text.trim().is_empty() {
+            return Some(Button::none());
+        }
+
+        text.split('+')
+            .try_fold(Button::none(), |set, name| Some(set | Button::from_name(name)?))
+    }
+
     /// Converts native VPAD inputs to generic gamepad input
     ///
     /// # Examples
@@ -576,6 +693,131 @@ impl Joystick {
             Some(a as u16)
         }
     }
+
+    /// Calculates the angle in radians, measured counterclockwise from the positive x axis (holding the joystick to the right).
+    ///
+    /// # Returns
+    ///
+    /// `None` if the joystick isn't moved, otherwise an angle in the range `-PI..=PI`. Holding the joystick straight up results in `PI / 2`.
+    #[inline]
+    pub fn angle_radians(&self) -> Option<f32> {
+        if self.x == 0.0 && self.y == 0.0 {
+            None
+        } else {
+            Some(self.y.atan2(self.x))
+        }
+    }
+
+    /// Quantizes the joystick into one of eight [Direction]s, each covering a 45° sector.
+    ///
+    /// Returns `None` if the joystick isn't moved. Combine it with [apply_deadzone][Joystick::apply_deadzone] to ignore small movements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wut::gamepad::{DeadZone, Direction};
+    ///
+    /// let direction = state
+    ///     .left_stick
+    ///     .and_then(|stick| stick.apply_deadzone(DeadZone::default()).direction8());
+    ///
+    /// match direction {
+    ///     Some(Direction::Up) => menu.previous(),
+    ///     Some(Direction::Down) => menu.next(),
+    ///     _ => (),
+    /// }
+    /// ```
+    #[inline]
+    pub fn direction8(&self) -> Option<Direction> {
+        let sector = (self.angle_radians()? / core::f32::consts::FRAC_PI_4).round() as i32;
+        Some(Direction::from_sector(sector))
+    }
+
+    /// Applies a [DeadZone] to the joystick.
+    ///
+    /// Magnitudes below `inner` are zeroed, the band between `inner` and `outer` is rescaled to `0.0..=1.0`, and magnitudes above `outer` are clamped to `1.0`. The direction of the joystick is preserved.
+    #[inline]
+    pub fn apply_deadzone(&self, dz: DeadZone) -> Joystick {
+        let magnitude = self.abs();
+        if magnitude <= dz.inner {
+            return Joystick::default();
+        }
+
+        let scaled = if dz.outer > dz.inner {
+            ((magnitude - dz.inner) / (dz.outer - dz.inner)).min(1.0)
+        } else {
+            1.0
+        };
+
+        Joystick {
+            x: self.x / magnitude * scaled,
+            y: self.y / magnitude * scaled,
+        }
+    }
+}
+
+impl From<Joystick> for Vec2 {
+    #[inline]
+    fn from(value: Joystick) -> Self {
+        Vec2::new(value.x, value.y)
+    }
+}
+
+/// One of eight directions of a [Joystick]. See [Joystick::direction8].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// Direction of the 45° sector `sector`, counted counterclockwise with `0` being [Right][Direction::Right].
+    fn from_sector(sector: i32) -> Self {
+        match sector.rem_euclid(8) {
+            0 => Self::Right,
+            1 => Self::UpRight,
+            2 => Self::Up,
+            3 => Self::UpLeft,
+            4 => Self::Left,
+            5 => Self::DownLeft,
+            6 => Self::Down,
+            _ => Self::DownRight,
+        }
+    }
+}
+
+/// Radial dead zone of a [Joystick].
+///
+/// Both `inner` and `outer` are magnitudes in the range `0.0..=1.0`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeadZone {
+    pub inner: f32,
+    pub outer: f32,
+}
+
+impl DeadZone {
+    #[inline]
+    pub fn new(inner: f32, outer: f32) -> Self {
+        debug_assert!((0.0..=1.0).contains(&inner));
+        debug_assert!((0.0..=1.0).contains(&outer));
+        debug_assert!(inner <= outer);
+        Self { inner, outer }
+    }
+}
+
+impl Default for DeadZone {
+    fn default() -> Self {
+        Self {
+            inner: 0.1,
+            outer: 0.95,
+        }
+    }
 }
 
 impl Into<Joystick> for sys::VPADVec2D {
@@ -623,6 +865,75 @@ impl Into<Joystick> for (f32, f32) {
     }
 }
 
+/// Represents a point on the DRC touchscreen.
+///
+/// The coordinates are calibrated to the screen resolution of the DRC.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TouchPoint {
+    pub x: u16,
+    pub y: u16,
+    /// `true` if the screen is touched and both coordinates are valid.
+    pub valid: bool,
+}
+
+impl Into<TouchPoint> for sys::VPADTouchData {
+    fn into(self) -> TouchPoint {
+        TouchPoint {
+            x: self.x,
+            y: self.y,
+            valid: self.touched != 0
+                && self.validity == sys::VPADTouchPadValidity::VPAD_VALID as u16,
+        }
+    }
+}
+
+impl Into<sys::VPADTouchData> for TouchPoint {
+    fn into(self) -> sys::VPADTouchData {
+        sys::VPADTouchData {
+            x: self.x,
+            y: self.y,
+            touched: self.valid.into(),
+            validity: if self.valid {
+                sys::VPADTouchPadValidity::VPAD_VALID as u16
+            } else {
+                (sys::VPADTouchPadValidity::VPAD_INVALID_X
+                    | sys::VPADTouchPadValidity::VPAD_INVALID_Y) as u16
+            },
+        }
+    }
+}
+
+/// Represents the IR pointer position of a Wiimote.
+///
+/// Coordinates are normalized to the screen, with `(0.0, 0.0)` at the center and `-1.0..=1.0` on both axes.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Pointer {
+    pub x: f32,
+    pub y: f32,
+    /// `true` if the pointer is locked on the sensor bar and within the screen.
+    pub valid: bool,
+}
+
+/// Convert a `VPADVec3D` or `KPADVec3D` into a [Vec3].
+macro_rules! vec3 {
+    ($v:expr) => {{
+        let v = $v;
+        Vec3::new(v.x, v.y, v.z)
+    }};
+}
+
+/// Motion sensor readings of a controller.
+///
+/// Values are reported as provided by the system: acceleration in units of g, angular velocity in rotations per second and angles in rotations (`1.0` is a full turn).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Motion {
+    pub accel: Vec3,
+    /// Zero for Wiimotes without a MotionPlus.
+    pub gyro: Vec3,
+    /// Orientation integrated from the gyroscope. Zero for Wiimotes without a MotionPlus.
+    pub angle: Vec3,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Port {
     DRC,
@@ -686,9 +997,64 @@ impl Into<u32> for Port {
     }
 }
 
+/// Kind of controller connected to a [Port].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Extension {
+    /// The GamePad.
+    Drc,
+    /// Wiimote without an extension.
+    Wiimote,
+    /// Wiimote with a Nunchuk.
+    Nunchuk,
+    /// Wiimote with a Classic Controller.
+    Classic,
+    /// Wii U Pro Controller.
+    Pro,
+    /// Any other extension, e.g. the Balance Board.
+    Unknown(u32),
+}
+
+impl From<sys::WPADExtensionType::Type> for Extension {
+    /// A MotionPlus is reported like the extension plugged into it.
+    fn from(value: sys::WPADExtensionType::Type) -> Self {
+        use sys::WPADExtensionType as Ext;
+        match value {
+            Ext::WPAD_EXT_CORE | Ext::WPAD_EXT_MPLUS => Self::Wiimote,
+            Ext::WPAD_EXT_NUNCHUK | Ext::WPAD_EXT_MPLUS_NUNCHUK => Self::Nunchuk,
+            Ext::WPAD_EXT_CLASSIC | Ext::WPAD_EXT_MPLUS_CLASSIC => Self::Classic,
+            Ext::WPAD_EXT_PRO_CONTROLLER => Self::Pro,
+            v => Self::Unknown(v as u32),
+        }
+    }
+}
+
+/// Battery charge of a wireless controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BatteryLevel {
+    Empty,
+    Low,
+    Medium,
+    High,
+    Full,
+    /// The controller is connected via cable and charging.
+    Charging,
+}
+
+impl From<u8> for BatteryLevel {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Empty,
+            1 => Self::Low,
+            2 => Self::Medium,
+            3 => Self::High,
+            _ => Self::Full,
+        }
+    }
+}
+
 /// Represent an arbitrary gamepad on the Wii U.
 ///
-/// Since some gamepads have no analog sticks (e.g. Wiimote) they are behind an `Option`. Special features (gyro, speakers, pointers, etc.) cannot be accesses with this.
+/// Since some gamepads have no analog sticks (e.g. Wiimote) they are behind an `Option`, just like the touchscreen, pointer and motion sensors. Other special features (speakers, etc.) cannot be accesses with this.
 pub struct Gamepad {
     pub port: Port,
     _resource: RrcGuard,
@@ -707,6 +1073,12 @@ pub struct State {
     pub release: FlagSet<Button>,
     pub left_stick: Option<Joystick>,
     pub right_stick: Option<Joystick>,
+    /// Touchscreen input. Only available on the DRC.
+    pub touch: Option<TouchPoint>,
+    /// IR pointer input. Only available on Wiimotes with an IR lock.
+    pub pointer: Option<Pointer>,
+    /// Motion sensor input. Only available on the DRC and Wiimotes.
+    pub motion: Option<Motion>,
 }
 
 impl State {
@@ -717,6 +1089,9 @@ impl State {
             release: unsafe { FlagSet::new_unchecked(0) },
             left_stick: None,
             right_stick: None,
+            touch: None,
+            pointer: None,
+            motion: None,
         }
     }
 
@@ -729,14 +1104,32 @@ impl State {
             release: Button::into_vpad(self.release),
             leftStick: self.left_stick.unwrap_or_default().into(),
             rightStick: self.right_stick.unwrap_or_default().into(),
+            tpNormal: self.touch.unwrap_or_default().into(),
             ..Default::default()
         }
     }
 
-    // #[inline]
-    // pub fn into_kpad(self) -> sys::KPADStatus {
-    //     todo!()
-    // }
+    // Convert generic gamepad input into native KPAD input
+    //
+    // The extension determines which extension status is filled with the buttons and joysticks.
+    #[inline]
+    pub fn into_kpad(self, extension: sys::WPADExtensionType::Type) -> sys::KPADStatus {
+        let mut status = sys::KPADStatus {
+            extensionType: extension as _,
+            ..Default::default()
+        };
+        status &= self;
+
+        if let Some(pointer) = self.pointer {
+            status.pos = sys::KPADVec2D {
+                x: pointer.x,
+                y: pointer.y,
+            };
+            status.posValid = if pointer.valid { 1 } else { 2 };
+        }
+
+        status
+    }
 }
 
 impl core::ops::BitOrAssign for State {
@@ -750,6 +1143,15 @@ impl core::ops::BitOrAssign for State {
         if let Some(stick) = rhs.right_stick {
             self.right_stick = stick.into();
         }
+        if let Some(touch) = rhs.touch {
+            self.touch = touch.into();
+        }
+        if let Some(pointer) = rhs.pointer {
+            self.pointer = pointer.into();
+        }
+        if let Some(motion) = rhs.motion {
+            self.motion = motion.into();
+        }
     }
 }
 
@@ -874,6 +1276,13 @@ impl From<sys::VPADStatus> for State {
             release: Button::from_vpad(value.release),
             left_stick: Some(value.leftStick.into()),
             right_stick: Some(value.rightStick.into()),
+            touch: Some(value.tpNormal.into()),
+            pointer: None,
+            motion: Some(Motion {
+                accel: vec3!(value.accelorometer.acc),
+                gyro: vec3!(value.gyro),
+                angle: vec3!(value.angle),
+            }),
         }
     }
 }
@@ -888,6 +1297,9 @@ impl From<sys::KPADStatus> for State {
             release: Button::from_kpad(value.release),
             left_stick: None,
             right_stick: None,
+            touch: None,
+            pointer: None,
+            motion: None,
         };
 
         match value.extensionType as Ext::Type {
@@ -913,6 +1325,34 @@ impl From<sys::KPADStatus> for State {
             },
             _ => (),
         }
+
+        // The Pro Controller has no IR camera; all other extensions are attached to a Wiimote.
+        if value.extensionType as Ext::Type != Ext::WPAD_EXT_PRO_CONTROLLER && value.posValid > 0 {
+            s.pointer = Some(Pointer {
+                x: value.pos.x,
+                y: value.pos.y,
+                valid: value.posValid == 1,
+            });
+        }
+
+        // The Pro Controller has no motion sensors either.
+        if value.extensionType as Ext::Type != Ext::WPAD_EXT_PRO_CONTROLLER {
+            let mut motion = Motion {
+                accel: vec3!(value.acc),
+                ..Default::default()
+            };
+
+            if matches!(
+                value.extensionType as Ext::Type,
+                Ext::WPAD_EXT_MPLUS | Ext::WPAD_EXT_MPLUS_NUNCHUK | Ext::WPAD_EXT_MPLUS_CLASSIC
+            ) {
+                motion.gyro = vec3!(value.mplus.mpls);
+                motion.angle = vec3!(value.mplus.angle);
+            }
+
+            s.motion = Some(motion);
+        }
+
         s
     }
 }
@@ -984,36 +1424,149 @@ impl Gamepad {
     }
 
     pub fn poll(&self) -> Result<State, GamepadError> {
-        match self.port {
-            Port::DRC => {
-                use sys::VPADReadError as E;
+        read(self.port)
+    }
 
-                let mut status = sys::VPADStatus::default();
-                let mut error = E::VPAD_READ_SUCCESS;
+    /// Motion sensor readings of the gamepad.
+    ///
+    /// Returns `None` for controllers without motion sensors or if no new sample is available. This reads a sample, so when the buttons are needed as well, use [State::motion] of [Gamepad::poll] instead.
+    pub fn motion(&self) -> Option<Motion> {
+        self.poll().ok()?.motion
+    }
+
+    /// Check if a controller is connected to the port.
+    ///
+    /// The DRC is always considered connected.
+    pub fn is_connected(&self) -> bool {
+        match self.port {
+            Port::DRC => true,
+            _ => {
+                let mut extension = sys::WPADExtensionType::Type::default();
+                (unsafe { sys::WPADProbe(self.port.into(), &mut extension) })
+                    == sys::WPADError::WPAD_ERROR_NONE
+            }
+        }
+    }
 
-                if unsafe { sys::VPADRead(self.port.into(), &mut status, 1, &mut error) } == 0
-                    && error != E::VPAD_READ_SUCCESS
+    /// Kind of controller connected to the port, e.g. to show matching button prompts.
+    ///
+    /// Returns [Extension::Drc] for the DRC port and `None` if no controller is connected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wut::font::icons;
+    /// use wut::gamepad::{Extension, Gamepad, Port};
+    ///
+    /// let button = match Gamepad::new(Port::Port0).extension() {
+    ///     Some(Extension::Wiimote | Extension::Nunchuk) => icons::WIIMOTE_BTN_A,
+    ///     _ => icons::BTN_A,
+    /// };
+    /// let prompt = format!("Press {button} to start");
+    /// ```
+    pub fn extension(&self) -> Option<Extension> {
+        match self.port {
+            Port::DRC => Some(Extension::Drc),
+            _ => {
+                let mut extension = sys::WPADExtensionType::Type::default();
+                if (unsafe { sys::WPADProbe(self.port.into(), &mut extension) })
+                    != sys::WPADError::WPAD_ERROR_NONE
                 {
-                    Err(GamepadError::try_from(error)?)
-                } else {
-                    Ok(State::from(status))
+                    return None;
                 }
+                Some(extension.into())
             }
-            _ => {
-                use sys::KPADError as E;
+        }
+    }
 
-                let mut status = sys::KPADStatus::default();
-                let mut error = E::KPAD_ERROR_OK;
+    /// Battery level of a wireless controller.
+    ///
+    /// Returns `None` for the DRC or if no controller is connected.
+    ///
+    /// The charging state of a Pro Controller is taken from the last sample read by [Gamepad::poll], so it is only reported once the controller has been polled.
+    pub fn battery_level(&self) -> Option<BatteryLevel> {
+        if self.port == Port::DRC {
+            return None;
+        }
 
-                if unsafe { sys::KPADReadEx(self.port.into(), &mut status, 1, &mut error) } == 0
-                    && error != E::KPAD_ERROR_OK
-                {
-                    Err(GamepadError::try_from(error)?)
+        let mut extension = sys::WPADExtensionType::Type::default();
+        if unsafe { sys::WPADProbe(self.port.into(), &mut extension) }
+            != sys::WPADError::WPAD_ERROR_NONE
+        {
+            return None;
+        }
+
+        let channel: u32 = self.port.into();
+        if extension == sys::WPADExtensionType::WPAD_EXT_PRO_CONTROLLER
+            && PRO_CHARGING.load(Ordering::Relaxed) & (1 << channel) != 0
+        {
+            return Some(BatteryLevel::Charging);
+        }
+
+        Some(BatteryLevel::from(unsafe {
+            sys::WPADGetBatteryLevel(self.port.into())
+        }))
+    }
+
+    /// Like [Gamepad::poll] but applies a [DeadZone] to both sticks.
+    pub fn poll_with_deadzone(&self, dz: DeadZone) -> Result<State, GamepadError> {
+        let mut state = self.poll()?;
+        state.left_stick = state.left_stick.map(|stick| stick.apply_deadzone(dz));
+        state.right_stick = state.right_stick.map(|stick| stick.apply_deadzone(dz));
+        Ok(state)
+    }
+
+    /// Vibrate the gamepad with a bit pattern.
+    ///
+    /// Every bit of `pattern` toggles the motor on (`1`) or off (`0`) for one step, starting at the most significant bit of the first byte. The pattern is played for `length`, but never longer than the pattern itself.
+    ///
+    /// # Note
+    ///
+    /// WPAD controllers only support switching the motor on or off. The motor is enabled if `pattern` contains any set bit and `length` is non-zero, and keeps running until [Gamepad::stop_rumble] is called.
+    pub fn rumble(&self, pattern: &[u8], length: Duration) -> Result<(), GamepadError> {
+        match self.port {
+            Port::DRC => {
+                let bits = (length.as_millis() * VPAD_MOTOR_PATTERN_RATE / 1000)
+                    .min(pattern.len() as u128 * 8)
+                    .min(VPAD_MOTOR_PATTERN_MAX_BITS) as u8;
+
+                if bits == 0 {
+                    self.stop_rumble()
                 } else {
-                    Ok(State::from(status))
+                    let mut pattern = pattern.to_vec();
+                    let status = unsafe {
+                        sys::VPADControlMotor(self.port.into(), pattern.as_mut_ptr(), bits)
+                    };
+
+                    // fails only for channels without a connected gamepad
+                    if status < 0 {
+                        Err(GamepadError::InvalidController)
+                    } else {
+                        Ok(())
+                    }
                 }
             }
+            _ => {
+                let enabled = !length.is_zero() && pattern.iter().any(|&b| b != 0);
+                unsafe {
+                    sys::WPADControlMotor(self.port.into(), enabled.into());
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Stop any ongoing vibration.
+    pub fn stop_rumble(&self) -> Result<(), GamepadError> {
+        match self.port {
+            Port::DRC => unsafe {
+                sys::VPADStopMotor(self.port.into());
+            },
+            _ => unsafe {
+                sys::WPADControlMotor(self.port.into(), 0);
+            },
         }
+        Ok(())
     }
 }
 
@@ -1028,6 +1581,92 @@ pub fn gamepads() -> alloc::vec::IntoIter<Gamepad> {
     pads.into_iter()
 }
 
+/// Read the current state of every connected gamepad.
+///
+/// Only ports which produced a fresh sample since the last read are returned, so an empty result means that there is no new input this frame. Unlike [gamepads], each subsystem is only initialized once per call, which makes this suitable for polling all input once per frame.
+///
+/// # Example
+///
+/// ```
+/// use wut::gamepad::{self, Button};
+///
+/// while wut::process::running() {
+///     for (port, state) in gamepad::poll_all() {
+///         if state.trigger.contains(Button::A) {
+///             println!("{port:?} pressed A");
+///         }
+///     }
+/// }
+/// ```
+pub fn poll_all() -> Vec<(Port, State)> {
+    let _vpad = VPAD.acquire();
+    let _kpad = KPAD.acquire();
+
+    Port::iter()
+        .filter(|&&port| {
+            let mut extension = sys::WPADExtensionType::Type::default();
+            port == Port::DRC
+                || unsafe { sys::WPADProbe(port.into(), &mut extension) }
+                    == sys::WPADError::WPAD_ERROR_NONE
+        })
+        .filter_map(|&port| read(port).ok().map(|state| (port, state)))
+        .collect()
+}
+
+/// Read one sample of `port`. The matching subsystem must be initialized by the caller.
+fn read(port: Port) -> Result<State, GamepadError> {
+    match port {
+        Port::DRC => {
+            use sys::VPADReadError as E;
+
+            let mut status = sys::VPADStatus::default();
+            let mut error = E::VPAD_READ_SUCCESS;
+
+            if unsafe { sys::VPADRead(port.into(), &mut status, 1, &mut error) } == 0
+                && error != E::VPAD_READ_SUCCESS
+            {
+                Err(GamepadError::try_from(error)?)
+            } else {
+                let raw = status.tpNormal;
+                unsafe {
+                    sys::VPADGetTPCalibratedPoint(port.into(), &mut status.tpNormal, &raw);
+                }
+                Ok(State::from(status))
+            }
+        }
+        _ => {
+            use sys::KPADError as E;
+
+            let mut status = sys::KPADStatus::default();
+            let mut error = E::KPAD_ERROR_OK;
+
+            let read = unsafe { sys::KPADReadEx(port.into(), &mut status, 1, &mut error) };
+            if read == 0 && error != E::KPAD_ERROR_OK {
+                Err(GamepadError::try_from(error)?)
+            } else {
+                if read > 0 {
+                    cache_charging(port, &status);
+                }
+                Ok(State::from(status))
+            }
+        }
+    }
+}
+
+/// Remember the charging state of a Pro Controller for [Gamepad::battery_level].
+fn cache_charging(port: Port, status: &sys::KPADStatus) {
+    let channel: u32 = port.into();
+    let charging = status.extensionType as sys::WPADExtensionType::Type
+        == sys::WPADExtensionType::WPAD_EXT_PRO_CONTROLLER
+        && unsafe { status.__bindgen_anon_1.pro.charging } != 0;
+
+    if charging {
+        PRO_CHARGING.fetch_or(1 << channel, Ordering::Relaxed);
+    } else {
+        PRO_CHARGING.fetch_and(!(1 << channel), Ordering::Relaxed);
+    }
+}
+
 pub fn max_gamepads() -> u8 {
     let _kpad = KPAD.acquire();
     unsafe { sys::KPADGetGameMaxControllers() }
@@ -0,0 +1,166 @@
+// tracker
+
+use super::{Button, Gamepad, GamepadError, Joystick, State};
+use crate::{
+    sync::{ConstMutex, LazyLock, Mutex},
+    time::{Duration, Instant},
+};
+use flagset::FlagSet;
+
+static BUTTON_REPEAT: ConstMutex<Option<ButtonRepeat>> = LazyLock::new(|| Mutex::new(None));
+
+#[derive(Debug, Clone, Copy)]
+struct ButtonRepeat {
+    delay: Duration,
+    interval: Duration,
+}
+
+/// Report held buttons as pressed again after `delay` and then every `interval`.
+///
+/// Applies to all [Tracker]s. The repeat starts over whenever another button is pressed, so only the last pressed combination of buttons is repeated. Repeated buttons are part of [StateDelta::pressed] and additionally reported in [StateDelta::repeated].
+///
+/// # Example
+///
+/// ```
+/// use wut::{gamepad::{self, Button}, time::Duration};
+///
+/// // scroll through the menu while up or down is held
+/// gamepad::set_button_repeat(Duration::from_millis(400), Duration::from_millis(80));
+///
+/// let delta = tracker.poll(&pad)?;
+/// if delta.pressed.contains(Button::Down) {
+///     menu.next();
+/// }
+/// ```
+pub fn set_button_repeat(delay: Duration, interval: Duration) {
+    if let Ok(mut repeat) = BUTTON_REPEAT.lock() {
+        *repeat = Some(ButtonRepeat { delay, interval });
+    }
+}
+
+/// Disable the button repeat configured by [set_button_repeat].
+pub fn clear_button_repeat() {
+    if let Ok(mut repeat) = BUTTON_REPEAT.lock() {
+        *repeat = None;
+    }
+}
+
+fn button_repeat() -> Option<ButtonRepeat> {
+    BUTTON_REPEAT.lock().ok().and_then(|repeat| *repeat)
+}
+
+/// Changes between two consecutive [State]s.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct StateDelta {
+    /// Buttons which are held now but were not held before, including [repeated][StateDelta::repeated] ones.
+    pub pressed: FlagSet<Button>,
+    /// Buttons which are reported as pressed because they were held long enough. See [set_button_repeat].
+    pub repeated: FlagSet<Button>,
+    /// Buttons which were held before but are not held now.
+    pub released: FlagSet<Button>,
+    /// Buttons which are currently held.
+    pub held: FlagSet<Button>,
+    /// Movement of the left stick. Zero if the stick is unavailable.
+    pub left_stick: Joystick,
+    /// Movement of the right stick. Zero if the stick is unavailable.
+    pub right_stick: Joystick,
+}
+
+/// Tracks the [State] of a gamepad across frames and reports changes.
+///
+/// # Example
+///
+/// ```
+/// use wut::gamepad::{Button, Gamepad, Port, Tracker};
+///
+/// let pad = Gamepad::new(Port::DRC);
+/// let mut tracker = Tracker::new();
+///
+/// while wut::process::running() {
+///     let delta = tracker.poll(&pad).unwrap();
+///     if delta.pressed.contains(Button::A) {
+///         println!("A was pressed");
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Tracker {
+    previous: State,
+    /// When the held buttons are repeated next.
+    repeat_at: Option<Instant>,
+}
+
+impl Tracker {
+    pub const fn new() -> Self {
+        Self {
+            previous: State::new(),
+            repeat_at: None,
+        }
+    }
+
+    /// The last known state.
+    #[inline]
+    pub fn state(&self) -> &State {
+        &self.previous
+    }
+
+    /// Store `new` as the current state and return the changes to the previous state.
+    pub fn update(&mut self, new: State) -> StateDelta {
+        let old = core::mem::replace(&mut self.previous, new);
+        let pressed = new.hold - old.hold;
+        let repeated = self.repeat(pressed, new.hold);
+
+        StateDelta {
+            pressed: pressed | repeated,
+            repeated,
+            released: old.hold - new.hold,
+            held: new.hold,
+            left_stick: stick_delta(old.left_stick, new.left_stick),
+            right_stick: stick_delta(old.right_stick, new.right_stick),
+        }
+    }
+
+    /// Poll `gamepad` and update the tracker.
+    ///
+    /// If no new samples are available, the last known state is reused, so no buttons are reported as pressed or released.
+    pub fn poll(&mut self, gamepad: &Gamepad) -> Result<StateDelta, GamepadError> {
+        match gamepad.poll() {
+            Ok(state) => Ok(self.update(state)),
+            Err(GamepadError::NoSamples) => Ok(self.update(self.previous)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Buttons which are due to be repeated.
+    fn repeat(&mut self, pressed: FlagSet<Button>, held: FlagSet<Button>) -> FlagSet<Button> {
+        let Some(repeat) = button_repeat().filter(|_| !held.is_empty()) else {
+            self.repeat_at = None;
+            return Button::none();
+        };
+
+        let now = Instant::now();
+        match self.repeat_at {
+            Some(at) if pressed.is_empty() => {
+                if now < at {
+                    return Button::none();
+                }
+                self.repeat_at = Some(now + repeat.interval);
+                held
+            }
+            _ => {
+                self.repeat_at = Some(now + repeat.delay);
+                Button::none()
+            }
+        }
+    }
+}
+
+fn stick_delta(old: Option<Joystick>, new: Option<Joystick>) -> Joystick {
+    match (old, new) {
+        (Some(old), Some(new)) => Joystick {
+            x: new.x - old.x,
+            y: new.y - old.y,
+        },
+        _ => Joystick::default(),
+    }
+}
@@ -1,4 +1,15 @@
-#[derive(Debug, Clone, Copy)]
+use core::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ColorParseError {
+    #[error("Hex color must have 3, 6 or 8 digits but has {0}")]
+    InvalidLength(usize),
+    #[error("Hex color contains non-hexadecimal character")]
+    InvalidCharacter,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Color {
     pub r: u8,
     pub g: u8,
@@ -7,6 +18,11 @@ pub struct Color {
 }
 
 impl Color {
+    #[inline]
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
     pub const fn transparent() -> Self {
         Self {
             r: 0,
@@ -97,6 +113,204 @@ impl Color {
         self.a = (255.0 * opacity) as u8;
         self
     }
+
+    /// Same color with alpha `a`.
+    #[inline]
+    pub const fn with_alpha(self, a: u8) -> Self {
+        self.alpha(a)
+    }
+
+    /// Linear interpolation between `self` and `other` for each channel including alpha.
+    ///
+    /// `t` is clamped to `0.0..=1.0`, where `0.0` returns `self` and `1.0` returns `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wut::gx2::color::Color;
+    ///
+    /// assert_eq!(Color::black().blend(Color::white(), 0.0), Color::black());
+    /// assert_eq!(Color::black().blend(Color::white(), 1.0), Color::white());
+    /// assert_eq!(Color::black().blend(Color::white(), 0.5), Color::new(128, 128, 128, 255));
+    /// ```
+    pub fn blend(self, other: Color, t: f32) -> Color {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t + 0.5) as u8;
+
+        Self {
+            r: lerp(self.r, other.r),
+            g: lerp(self.g, other.g),
+            b: lerp(self.b, other.b),
+            a: lerp(self.a, other.a),
+        }
+    }
+
+    /// Composite `self` on top of `background` using straight (non-premultiplied) alpha.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wut::gx2::color::Color;
+    ///
+    /// assert_eq!(Color::red().over(Color::blue()), Color::red());
+    /// assert_eq!(Color::transparent().over(Color::blue()), Color::blue());
+    /// assert_eq!(Color::red().with_alpha(128).over(Color::blue()), Color::new(128, 0, 127, 255));
+    /// ```
+    pub fn over(self, background: Color) -> Color {
+        let alpha = self.a as f32 / 255.0;
+        let background_alpha = background.a as f32 / 255.0 * (1.0 - alpha);
+        let out = alpha + background_alpha;
+
+        if out <= 0.0 {
+            return Self::transparent();
+        }
+
+        let channel =
+            |f: u8, b: u8| ((f as f32 * alpha + b as f32 * background_alpha) / out + 0.5) as u8;
+
+        Self {
+            r: channel(self.r, background.r),
+            g: channel(self.g, background.g),
+            b: channel(self.b, background.b),
+            a: (out * 255.0 + 0.5) as u8,
+        }
+    }
+
+    /// Parse a color from a hex string.
+    ///
+    /// Accepts `#RGB`, `#RRGGBB`, and `#RRGGBBAA`. The leading `#` is optional. Colors without alpha are opaque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wut::gx2::color::Color;
+    ///
+    /// let color = Color::from_hex("#f80").unwrap();
+    /// assert_eq!(Color::from_hex("ff8800ff").unwrap(), color);
+    /// ```
+    pub fn from_hex(hex: &str) -> Result<Self, ColorParseError> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(ColorParseError::InvalidCharacter);
+        }
+
+        let digit = |i: usize| -> u8 {
+            let b = hex.as_bytes()[i];
+            match b {
+                b'0'..=b'9' => b - b'0',
+                b'a'..=b'f' => b - b'a' + 10,
+                _ => b - b'A' + 10,
+            }
+        };
+        let byte = |i: usize| digit(2 * i) << 4 | digit(2 * i + 1);
+
+        match hex.len() {
+            3 => Ok(Self {
+                r: digit(0) * 0x11,
+                g: digit(1) * 0x11,
+                b: digit(2) * 0x11,
+                a: 255,
+            }),
+            6 => Ok(Self {
+                r: byte(0),
+                g: byte(1),
+                b: byte(2),
+                a: 255,
+            }),
+            8 => Ok(Self {
+                r: byte(0),
+                g: byte(1),
+                b: byte(2),
+                a: byte(3),
+            }),
+            len => Err(ColorParseError::InvalidLength(len)),
+        }
+    }
+
+    /// Channels in the order of `GX2_SURFACE_FORMAT_UNORM_R8_G8_B8_A8` surfaces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wut::gx2::color::Color;
+    ///
+    /// let color = Color::new(0x12, 0x34, 0x56, 0x78);
+    /// assert_eq!(color.to_rgba8(), [0x12, 0x34, 0x56, 0x78]);
+    /// assert_eq!(Color::from_rgba8(color.to_rgba8()), color);
+    /// ```
+    #[inline]
+    pub const fn to_rgba8(self) -> [u8; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+
+    #[inline]
+    pub const fn from_rgba8(rgba: [u8; 4]) -> Self {
+        Self::new(rgba[0], rgba[1], rgba[2], rgba[3])
+    }
+
+    /// Channels in the order of BGRA8 surfaces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wut::gx2::color::Color;
+    ///
+    /// assert_eq!(Color::new(0x12, 0x34, 0x56, 0x78).to_bgra8(), [0x56, 0x34, 0x12, 0x78]);
+    /// ```
+    #[inline]
+    pub const fn to_bgra8(self) -> [u8; 4] {
+        [self.b, self.g, self.r, self.a]
+    }
+
+    /// Pack into 16 bit RGB565 with red in the most significant bits. Alpha is dropped and the lower bits of each channel are truncated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wut::gx2::color::Color;
+    ///
+    /// assert_eq!(Color::white().to_rgb565(), 0xffff);
+    /// assert_eq!(Color::red().to_rgb565(), 0xf800);
+    /// assert_eq!(Color::new(0x12, 0x34, 0x56, 0x78).to_rgb565(), 0x11aa);
+    /// ```
+    #[inline]
+    pub const fn to_rgb565(self) -> u16 {
+        (self.r as u16 >> 3) << 11 | (self.g as u16 >> 2) << 5 | self.b as u16 >> 3
+    }
+
+    /// Unpack a 16 bit RGB565 color. The result is opaque and each channel is scaled to the full `0..=255` range.
+    ///
+    /// Converting back with [to_rgb565][Color::to_rgb565] results in the same value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wut::gx2::color::Color;
+    ///
+    /// assert_eq!(Color::from_rgb565(0xffff), Color::white());
+    /// assert_eq!(Color::from_rgb565(0x001f), Color::blue());
+    /// for rgb565 in 0..=u16::MAX {
+    ///     assert_eq!(Color::from_rgb565(rgb565).to_rgb565(), rgb565);
+    /// }
+    /// ```
+    #[inline]
+    pub const fn from_rgb565(rgb565: u16) -> Self {
+        let r = (rgb565 >> 11) as u8 & 0x1f;
+        let g = (rgb565 >> 5) as u8 & 0x3f;
+        let b = rgb565 as u8 & 0x1f;
+
+        // repeat the upper bits in the lower ones, so 0x1f becomes 0xff
+        Self::new(r << 3 | r >> 2, g << 2 | g >> 4, b << 3 | b >> 2, 255)
+    }
+}
+
+impl FromStr for Color {
+    type Err = ColorParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(s)
+    }
 }
 
 impl Into<(f32, f32, f32, f32)> for Color {
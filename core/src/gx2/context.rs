@@ -1,7 +1,30 @@
-use crate::rrc::RrcGuard;
-use core::marker::PhantomData;
+use crate::{
+    foreground::MemoryReclaim,
+    ptr::DmaBuffer,
+    rrc::RrcGuard,
+    sync::{LazyLock, Mutex},
+};
+use core::{
+    marker::PhantomData,
+    sync::atomic::{AtomicU32, Ordering},
+};
 use wut_sys as sys;
 
+/// Number of finished frames. WHBGfx waits for the GPU when finishing a frame, so buffers used by previous frames are free again.
+static FRAME: AtomicU32 = AtomicU32::new(0);
+
+/// Number of the frame which is currently rendered.
+pub(crate) fn frame() -> u32 {
+    FRAME.load(Ordering::Acquire)
+}
+
+fn finish_render() {
+    unsafe {
+        sys::WHBGfxFinishRender();
+    }
+    FRAME.fetch_add(1, Ordering::AcqRel);
+}
+
 pub struct RenderContext {
     _resource: RrcGuard,
 }
@@ -13,11 +36,178 @@ impl RenderContext {
         }
     }
 
+    /// Like [new][RenderContext::new], but presents frames with the given number of scan buffers.
+    ///
+    /// The setting applies to both screens and all render contexts. It is kept when the application returns to the foreground.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wut::gx2::{BufferMode, RenderContext};
+    ///
+    /// let context = RenderContext::with_buffering(BufferMode::Triple);
+    /// ```
+    pub fn with_buffering(mode: BufferMode) -> Self {
+        let context = Self::new();
+        set_buffering(mode);
+        context
+    }
+
     pub fn ready(&self) -> Context<Ready> {
         Context::new(self)
     }
 }
 
+// region: Scan Buffers
+
+/// Number of scan buffers frames are presented from.
+///
+/// With [Double][BufferMode::Double] buffering, the GPU renders into one buffer while the other one is displayed. If a frame is finished before the previous one was shown, rendering stalls until the next vertical retrace.
+///
+/// [Triple][BufferMode::Triple] buffering adds a third buffer, so the GPU can start the next frame right away. This avoids stalls when frame times vary, but may add up to one frame of latency and requires an additional buffer per screen (about 8 MiB for the TV at 1080p and 1.6 MiB for the GamePad).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BufferMode {
+    #[default]
+    Double,
+    Triple,
+}
+
+impl From<BufferMode> for sys::GX2BufferingMode::Type {
+    fn from(value: BufferMode) -> Self {
+        match value {
+            BufferMode::Double => sys::GX2BufferingMode::GX2_BUFFERING_MODE_DOUBLE,
+            BufferMode::Triple => sys::GX2BufferingMode::GX2_BUFFERING_MODE_TRIPLE,
+        }
+    }
+}
+
+const SCAN_BUFFER_ALIGNMENT: usize = 0x1000;
+const SCAN_BUFFER_FORMAT: sys::GX2SurfaceFormat::Type =
+    sys::GX2SurfaceFormat::GX2_SURFACE_FORMAT_UNORM_R8_G8_B8_A8;
+
+/// Scan buffers replacing the ones allocated by WHBGfx. `None` while the default double buffering is used.
+static SCAN_BUFFERS: LazyLock<Mutex<Option<ScanBuffers>>> = LazyLock::new(|| Mutex::new(None));
+
+struct ScanBuffers {
+    mode: BufferMode,
+    tv: DmaBuffer,
+    drc: DmaBuffer,
+}
+
+impl ScanBuffers {
+    /// Make the GPU present from these buffers.
+    fn apply(&mut self) {
+        let mode = self.mode.into();
+
+        unsafe {
+            sys::GX2DrawDone();
+
+            sys::GX2Invalidate(
+                sys::GX2InvalidateMode::GX2_INVALIDATE_MODE_CPU,
+                self.tv.as_mut_ptr() as *mut _,
+                self.tv.len() as u32,
+            );
+            sys::GX2SetTVBuffer(
+                self.tv.as_mut_ptr() as *mut _,
+                self.tv.len() as u32,
+                tv_render_mode(),
+                SCAN_BUFFER_FORMAT,
+                mode,
+            );
+
+            sys::GX2Invalidate(
+                sys::GX2InvalidateMode::GX2_INVALIDATE_MODE_CPU,
+                self.drc.as_mut_ptr() as *mut _,
+                self.drc.len() as u32,
+            );
+            sys::GX2SetDRCBuffer(
+                self.drc.as_mut_ptr() as *mut _,
+                self.drc.len() as u32,
+                sys::GX2DrcRenderMode::GX2_DRC_RENDER_MODE_SINGLE,
+                SCAN_BUFFER_FORMAT,
+                mode,
+            );
+        }
+    }
+}
+
+/// Same render mode WHBGfx chooses for the TV.
+fn tv_render_mode() -> sys::GX2TVRenderMode::Type {
+    use sys::GX2TVScanMode as S;
+    match unsafe { sys::GX2GetSystemTVScanMode() } {
+        S::GX2_TV_SCAN_MODE_480I | S::GX2_TV_SCAN_MODE_480P => {
+            sys::GX2TVRenderMode::GX2_TV_RENDER_MODE_WIDE_480P
+        }
+        S::GX2_TV_SCAN_MODE_1080I | S::GX2_TV_SCAN_MODE_1080P => {
+            sys::GX2TVRenderMode::GX2_TV_RENDER_MODE_WIDE_1080P
+        }
+        _ => sys::GX2TVRenderMode::GX2_TV_RENDER_MODE_WIDE_720P,
+    }
+}
+
+fn set_buffering(mode: BufferMode) {
+    let mut buffers = SCAN_BUFFERS.lock().unwrap();
+
+    // WHBGfx already uses double buffering
+    if buffers.is_none() && mode == BufferMode::Double {
+        return;
+    }
+
+    let (mut tv_size, mut drc_size, mut unknown) = (0, 0, 0);
+    unsafe {
+        sys::GX2CalcTVSize(
+            tv_render_mode(),
+            SCAN_BUFFER_FORMAT,
+            mode.into(),
+            &mut tv_size,
+            &mut unknown,
+        );
+        sys::GX2CalcDRCSize(
+            sys::GX2DrcRenderMode::GX2_DRC_RENDER_MODE_SINGLE,
+            SCAN_BUFFER_FORMAT,
+            mode.into(),
+            &mut drc_size,
+            &mut unknown,
+        );
+    }
+
+    // the previous buffers must stay alive until the new ones are in use
+    let previous = buffers.take();
+    let mut next = match previous {
+        Some(b) if b.tv.len() >= tv_size as usize && b.drc.len() >= drc_size as usize => {
+            ScanBuffers { mode, ..b }
+        }
+        _ => ScanBuffers {
+            mode,
+            tv: DmaBuffer::with_alignment(tv_size as usize, SCAN_BUFFER_ALIGNMENT),
+            drc: DmaBuffer::with_alignment(drc_size as usize, SCAN_BUFFER_ALIGNMENT),
+        },
+    };
+    next.apply();
+    *buffers = Some(next);
+    // keep lock scopes disjoint, the reclaimer below takes SCAN_BUFFERS on its own
+    drop(buffers);
+
+    // WHBGfx restores its own buffers when returning to the foreground
+    MemoryReclaim::register(
+        "gx2::scan_buffers",
+        MemoryReclaim::GRAPHICS,
+        || (),
+        || {
+            if let Some(buffers) = SCAN_BUFFERS.lock().unwrap().as_mut() {
+                buffers.apply();
+            }
+        },
+    );
+}
+
+/// Free the scan buffers after WHBGfx was shut down.
+pub(crate) fn release_scan_buffers() {
+    SCAN_BUFFERS.lock().unwrap().take();
+}
+
+// endregion
+
 // region: Context State
 
 pub trait State {
@@ -27,7 +217,10 @@ pub trait State {
 pub struct Ready;
 pub struct Tv;
 pub struct Drc;
-pub trait TvOrDrc: State {}
+pub trait TvOrDrc: State {
+    /// Color buffer of the screen.
+    fn color_buffer() -> *mut sys::GX2ColorBuffer;
+}
 
 impl State for Ready {
     fn on_ready() {
@@ -65,8 +258,16 @@ impl State for Drc {
     }
 }
 
-impl TvOrDrc for Tv {}
-impl TvOrDrc for Drc {}
+impl TvOrDrc for Tv {
+    fn color_buffer() -> *mut sys::GX2ColorBuffer {
+        unsafe { sys::WHBGfxGetTVColourBuffer() }
+    }
+}
+impl TvOrDrc for Drc {
+    fn color_buffer() -> *mut sys::GX2ColorBuffer {
+        unsafe { sys::WHBGfxGetDRCColourBuffer() }
+    }
+}
 
 // endregion
 pub struct Context<'a, S: State> {
@@ -103,6 +304,18 @@ impl<'a> Context<'a, Ready> {
     }
 }
 
+impl<S: TvOrDrc> Context<'_, S> {
+    /// Size of the screen that is currently rendered to in pixels.
+    pub fn size(&self) -> (u32, u32) {
+        let buffer = S::color_buffer();
+        if buffer.is_null() {
+            (0, 0)
+        } else {
+            unsafe { ((*buffer).surface.width, (*buffer).surface.height) }
+        }
+    }
+}
+
 impl<'a> Context<'a, Drc> {
     pub fn tv(self) -> Context<'a, Tv> {
         Context::transition(self)
@@ -110,9 +323,7 @@ impl<'a> Context<'a, Drc> {
 
     pub fn finish(self) {
         drop(self);
-        unsafe {
-            sys::WHBGfxFinishRender();
-        }
+        finish_render();
     }
 }
 
@@ -123,9 +334,7 @@ impl<'a> Context<'a, Tv> {
 
     pub fn finish(self) {
         drop(self);
-        unsafe {
-            sys::WHBGfxFinishRender();
-        }
+        finish_render();
     }
 }
 
@@ -1,5 +1,14 @@
 use wut_sys as sys;
 
+/// Outcome of a closed dialog, see [KeyboardRenderer::poll][super::keyboard::KeyboardRenderer::poll] and [ErrorViewRenderer::poll][super::error_view::ErrorViewRenderer::poll].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DialogResult<T> {
+    /// The dialog was confirmed.
+    Ok(T),
+    /// The dialog was cancelled.
+    Cancel,
+}
+
 pub enum Language {
     Japanese,
     English,
@@ -7,7 +7,7 @@
 //! ```
 //! use wut::gx2::{
 //!     error_view::{Format, Target, Config},
-//!     ErrorView, Language, Region, RenderContext, Renderable
+//!     DialogResult, ErrorView, Language, Region, RenderContext, Renderable
 //! };
 //!
 //! let error_view = ErrorView::new(Region::Europe, Language::English).unwrap();
@@ -24,8 +24,8 @@
 //! while wut::process:running() {
 //!     render.update();
 //!
-//!     if render.is_ok() {
-//!         println!("{:?}", render.get_value());
+//!     if let Some(result) = render.poll() {
+//!         println!("{}", result == DialogResult::Ok(()));
 //!         break;
 //!     }
 //!
@@ -41,12 +41,12 @@
 //! ```
 
 use crate::{
+    ffi::into_utf16,
     gx2::{
         Renderable,
-        dialog_utils::{Controller, ControllerInfo, Language, Region},
+        dialog_utils::{Controller, ControllerInfo, DialogResult, Language, Region},
     },
     rrc::RrcGuard,
-    utils::into_utf16,
 };
 use alloc::{vec, vec::Vec};
 use core::marker::PhantomData;
@@ -279,6 +279,19 @@ impl<'a> ErrorViewRenderer<'a> {
         unsafe { sys::nn_erreula_IsDecideSelectRightButtonError() }
     }
 
+    /// Check if a button of the error view was pressed.
+    ///
+    /// Returns `None` while no button was pressed.
+    pub fn poll(&self) -> Option<DialogResult<()>> {
+        if self.is_ok() {
+            Some(DialogResult::Ok(()))
+        } else if self.is_cancel() {
+            Some(DialogResult::Cancel)
+        } else {
+            None
+        }
+    }
+
     pub fn get_value(self) -> (i32, i32) {
         unsafe {
             (
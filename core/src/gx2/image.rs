@@ -0,0 +1,276 @@
+//! Image decoders used by [Texture::from_file][super::texture::Texture::from_file]
+
+use super::{color::Color, texture::TextureError};
+use alloc::{vec, vec::Vec};
+
+/// A decoded image with pixels stored row by row starting at the top left corner.
+pub(crate) struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<Color>,
+}
+
+impl Image {
+    /// Decode a PNG or TGA image. The format is detected from the content.
+    pub fn decode(data: &[u8]) -> Result<Self, TextureError> {
+        if data.starts_with(PNG_SIGNATURE) {
+            decode_png(data)
+        } else {
+            decode_tga(data)
+        }
+    }
+}
+
+// region: PNG
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+#[cfg(not(feature = "image"))]
+fn decode_png(_data: &[u8]) -> Result<Image, TextureError> {
+    Err(TextureError::UnsupportedFormat)
+}
+
+/// Decode a non-interlaced PNG with a bit depth of 8.
+#[cfg(feature = "image")]
+fn decode_png(data: &[u8]) -> Result<Image, TextureError> {
+    let mut rest = &data[PNG_SIGNATURE.len()..];
+    let mut header = None;
+    let mut palette: &[u8] = &[];
+    let mut transparency: &[u8] = &[];
+    let mut compressed = Vec::new();
+
+    while rest.len() >= 12 {
+        let length = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+        let kind = &rest[4..8];
+        let Some(chunk) = rest[8..].get(..length) else {
+            return Err(TextureError::InvalidImage("truncated chunk"));
+        };
+
+        match kind {
+            b"IHDR" if length >= 13 => header = Some(chunk),
+            b"PLTE" => palette = chunk,
+            b"tRNS" => transparency = chunk,
+            b"IDAT" => compressed.extend_from_slice(chunk),
+            b"IEND" => break,
+            _ => (),
+        }
+
+        // skip data and crc
+        rest = length
+            .checked_add(12)
+            .and_then(|end| rest.get(end..))
+            .unwrap_or_default();
+    }
+
+    let header = header.ok_or(TextureError::InvalidImage("missing header"))?;
+    let width = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+    let height = u32::from_be_bytes([header[4], header[5], header[6], header[7]]);
+    let (depth, color_type, interlace) = (header[8], header[9], header[12]);
+
+    if depth != 8 || interlace != 0 {
+        return Err(TextureError::UnsupportedFormat);
+    }
+
+    let channels = match color_type {
+        0 | 3 => 1,
+        4 => 2,
+        2 => 3,
+        6 => 4,
+        _ => return Err(TextureError::InvalidImage("invalid color type")),
+    };
+
+    let raw = miniz_oxide::inflate::decompress_to_vec_zlib(&compressed)
+        .map_err(|_| TextureError::InvalidImage("corrupt image data"))?;
+
+    // every scanline starts with its filter type
+    let stride = (width as usize).checked_mul(channels);
+    let size = stride.and_then(|stride| stride.checked_add(1)?.checked_mul(height as usize));
+    let (Some(stride), Some(size)) = (stride, size) else {
+        return Err(TextureError::InvalidImage("image too large"));
+    };
+    if raw.len() < size {
+        return Err(TextureError::InvalidImage("not enough image data"));
+    }
+
+    let pixels = unfilter(&raw, stride, height as usize, channels)?;
+
+    let pixels = pixels
+        .chunks_exact(channels)
+        .map(|p| match color_type {
+            0 => Color::new(p[0], p[0], p[0], 255),
+            4 => Color::new(p[0], p[0], p[0], p[1]),
+            2 => Color::new(p[0], p[1], p[2], 255),
+            6 => Color::new(p[0], p[1], p[2], p[3]),
+            _ => {
+                let i = p[0] as usize;
+                match palette.get(i * 3..i * 3 + 3) {
+                    Some(rgb) => {
+                        Color::new(rgb[0], rgb[1], rgb[2], *transparency.get(i).unwrap_or(&255))
+                    }
+                    None => Color::transparent(),
+                }
+            }
+        })
+        .collect();
+
+    Ok(Image {
+        width,
+        height,
+        pixels,
+    })
+}
+
+/// Reverse the per-scanline PNG filters.
+#[cfg(feature = "image")]
+fn unfilter(
+    raw: &[u8],
+    stride: usize,
+    height: usize,
+    channels: usize,
+) -> Result<Vec<u8>, TextureError> {
+    let mut out = vec![0u8; stride * height];
+
+    for y in 0..height {
+        let filter = raw[y * (stride + 1)];
+        let line = &raw[y * (stride + 1) + 1..(y + 1) * (stride + 1)];
+        let (previous, current) = out.split_at_mut(y * stride);
+        let above = if y == 0 {
+            None
+        } else {
+            Some(&previous[(y - 1) * stride..])
+        };
+        let current = &mut current[..stride];
+
+        for x in 0..stride {
+            let a = if x >= channels {
+                current[x - channels]
+            } else {
+                0
+            };
+            let b = above.map_or(0, |above| above[x]);
+            let c = match above {
+                Some(above) if x >= channels => above[x - channels],
+                _ => 0,
+            };
+
+            current[x] = line[x].wrapping_add(match filter {
+                0 => 0,
+                1 => a,
+                2 => b,
+                3 => ((a as u16 + b as u16) / 2) as u8,
+                4 => paeth(a, b, c),
+                _ => return Err(TextureError::InvalidImage("invalid filter")),
+            });
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(feature = "image")]
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let (pa, pb, pc) = (
+        (p - a as i16).abs(),
+        (p - b as i16).abs(),
+        (p - c as i16).abs(),
+    );
+
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
+
+// endregion
+
+// region: TGA
+
+/// Decode an uncompressed or run-length encoded true color TGA with 24 or 32 bits per pixel.
+fn decode_tga(data: &[u8]) -> Result<Image, TextureError> {
+    if data.len() < 18 {
+        return Err(TextureError::UnsupportedFormat);
+    }
+
+    let id_length = data[0] as usize;
+    let (color_map, kind) = (data[1], data[2]);
+    let width = u16::from_le_bytes([data[12], data[13]]) as u32;
+    let height = u16::from_le_bytes([data[14], data[15]]) as u32;
+    let (depth, descriptor) = (data[16], data[17]);
+
+    let rle = match kind {
+        2 => false,
+        10 => true,
+        _ => return Err(TextureError::UnsupportedFormat),
+    };
+    if color_map != 0 || !(depth == 24 || depth == 32) {
+        return Err(TextureError::UnsupportedFormat);
+    }
+
+    let bytes = depth as usize / 8;
+    let count = width as usize * height as usize;
+    let mut input = data.get(18 + id_length..).unwrap_or_default();
+    if !rle && input.len() / bytes < count {
+        return Err(TextureError::InvalidImage("not enough image data"));
+    }
+    // run-length encoded data may expand, so only reserve what is certainly needed
+    let mut pixels = Vec::with_capacity(count.min(input.len() / bytes));
+
+    let mut read = |input: &mut &[u8]| -> Result<Color, TextureError> {
+        let Some(p) = input.get(..bytes) else {
+            return Err(TextureError::InvalidImage("not enough image data"));
+        };
+        *input = &input[bytes..];
+        // stored as BGR(A)
+        Ok(Color::new(
+            p[2],
+            p[1],
+            p[0],
+            if bytes == 4 { p[3] } else { 255 },
+        ))
+    };
+
+    while pixels.len() < count {
+        if rle {
+            let Some((&packet, next)) = input.split_first() else {
+                return Err(TextureError::InvalidImage("not enough image data"));
+            };
+            input = next;
+            let n = (packet & 0x7F) as usize + 1;
+
+            if packet & 0x80 != 0 {
+                let color = read(&mut input)?;
+                pixels.extend(core::iter::repeat_n(color, n));
+            } else {
+                for _ in 0..n {
+                    pixels.push(read(&mut input)?);
+                }
+            }
+        } else {
+            pixels.push(read(&mut input)?);
+        }
+    }
+    pixels.truncate(count);
+
+    // origin is bottom left unless bit 5 of the descriptor is set
+    if descriptor & 0x20 == 0 && width > 0 {
+        let rows: Vec<_> = pixels
+            .chunks_exact(width as usize)
+            .rev()
+            .flatten()
+            .copied()
+            .collect();
+        pixels = rows;
+    }
+
+    Ok(Image {
+        width,
+        height,
+        pixels,
+    })
+}
+
+// endregion
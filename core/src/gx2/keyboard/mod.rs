@@ -6,8 +6,8 @@
 //!
 //! ```
 //! use wut::gx2::{
-//!     keyboard::Config, Controller, Keyboard, Language, Region, RenderContext,
-//!     Renderable,
+//!     keyboard::Config, Controller, DialogResult, Keyboard, Language, Region,
+//!     RenderContext, Renderable,
 //! };
 //!
 //! let keyboard = Keyboard::new(Region::Europe).unwrap();
@@ -24,9 +24,13 @@
 //! while wut::process:running() {
 //!     render.update();
 //!
-//!     if render.is_ok() {
-//!         println!("{:?}", render.get_value());
-//!         break;
+//!     match render.poll() {
+//!         Some(DialogResult::Ok(value)) => {
+//!             println!("{value}");
+//!             break;
+//!         }
+//!         Some(DialogResult::Cancel) => break,
+//!         None => (),
 //!     }
 //!
 //!     let context = context.ready();
@@ -41,12 +45,12 @@
 //! ```
 
 use crate::{
+    ffi::{from_utf16, into_utf16},
     gx2::{
         Renderable,
-        dialog_utils::{Controller, ControllerInfo, Language, Region},
+        dialog_utils::{Controller, ControllerInfo, DialogResult, Language, Region},
     },
     rrc::RrcGuard,
-    utils::{from_utf16, into_utf16},
 };
 use alloc::{string::String, vec, vec::Vec};
 use core::marker::PhantomData;
@@ -320,9 +324,29 @@ impl<'a> KeyboardRenderer<'a> {
         unsafe { sys::nn_swkbd_IsDecideCancelButton(core::ptr::null_mut()) }
     }
 
+    /// Check if the keyboard was closed.
+    ///
+    /// Returns the typed text if it was confirmed and `None` while the keyboard is still open.
+    pub fn poll(&self) -> Option<DialogResult<String>> {
+        if self.is_ok() {
+            Some(DialogResult::Ok(self.value()))
+        } else if self.is_cancel() {
+            Some(DialogResult::Cancel)
+        } else {
+            None
+        }
+    }
+
+    /// Current content of the input form.
+    ///
+    /// Unlike [get_value][KeyboardRenderer::get_value], the keyboard stays open, so this may be called repeatedly, e.g. every frame to preview the typed text.
+    pub fn value(&self) -> String {
+        unsafe { from_utf16(sys::nn_swkbd_GetInputFormString()) }
+    }
+
+    /// Final content of the input form. Closes the keyboard.
     pub fn get_value(self) -> String {
-        let value = unsafe { sys::nn_swkbd_GetInputFormString() };
-        from_utf16(value)
+        self.value()
     }
 }
 
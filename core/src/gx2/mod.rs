@@ -4,17 +4,21 @@
 
 pub mod color;
 mod dialog_utils;
+mod image;
 pub mod error_view;
 pub mod keyboard;
 // pub mod render;
 pub mod context;
 pub mod screen;
 pub mod shader;
+pub mod sprite;
+pub mod texture;
 
-pub use context::RenderContext;
-pub use dialog_utils::{Controller, Language, Region};
+pub use context::{BufferMode, RenderContext};
+pub use dialog_utils::{Controller, DialogResult, Language, Region};
 pub use error_view::ErrorView;
 pub use keyboard::Keyboard;
+pub use texture::Texture;
 
 use crate::rrc::Rrc;
 use wut_sys as sys;
@@ -26,6 +30,7 @@ pub(crate) static GFX: Rrc = Rrc::new(
     },
     || unsafe {
         sys::WHBGfxShutdown();
+        context::release_scan_buffers();
     },
 );
 
@@ -22,6 +22,19 @@ impl<T: AttributeFormat> Attribute<T> {
         name: &str,
         index: u32,
         offset: u32,
+    ) -> Result<Self, ()> {
+        Self::with_format(group, name, index, offset, T::gx2_attribute_format())
+    }
+
+    /// Like [Attribute::new], but the shader reads the buffer data as `format` instead of the format of `T`.
+    ///
+    /// This allows e.g. reading [Uint4] data as normalized values. `format` must have the same size as `T`.
+    pub fn with_format(
+        group: &mut sys::WHBGfxShaderGroup,
+        name: &str,
+        index: u32,
+        offset: u32,
+        format: sys::GX2AttribFormat::Type,
     ) -> Result<Self, ()> {
         let s = Self {
             index,
@@ -36,7 +49,7 @@ impl<T: AttributeFormat> Attribute<T> {
                 name.as_ptr(),
                 s.index,
                 s.offset,
-                T::gx2_attribute_format(),
+                format,
             )
         } == 0
         {
@@ -0,0 +1,137 @@
+use crate::{
+    fs::{self, FilesystemError},
+    path::Path,
+    rrc::RrcGuard,
+};
+use thiserror::Error;
+use wut_sys as sys;
+
+#[derive(Debug, Error)]
+pub enum ShaderError {
+    #[error("Failed to read shader file")]
+    Io(#[from] FilesystemError),
+    #[error("Invalid GFD shader file: {0}")]
+    InvalidFormat(&'static str),
+    #[error("Shader file contains no shader group with index {0}")]
+    NotFound(u32),
+    #[error("Failed to load shader into GPU memory")]
+    LoadFailed,
+    #[error("Shader attributes cannot be initialized")]
+    InvalidAttributes,
+}
+
+const FILE_MAGIC: &[u8] = b"Gfx2";
+const BLOCK_MAGIC: &[u8] = b"BLK{";
+const HEADER_SIZE: usize = 0x20;
+const BLOCK_END_OF_FILE: u32 = 1;
+
+/// Vertex and pixel shader loaded from a compiled `*.gsh` (GFD) file.
+///
+/// Combine it with the attributes of the vertex shader via [Shader::from_group][super::Shader::from_group] to render with it.
+///
+/// # Example
+///
+/// ```
+/// use wut::gx2::shader::{Shader, ShaderGroup};
+///
+/// let group = ShaderGroup::from_file("/vol/content/shaders/sprite.gsh")?;
+/// let shader: Shader<SpriteAttributes> = Shader::from_group(group)?;
+/// ```
+pub struct ShaderGroup {
+    group: sys::WHBGfxShaderGroup,
+    _resource: RrcGuard,
+}
+
+impl ShaderGroup {
+    /// Load the first vertex and pixel shader of a GFD file.
+    #[inline]
+    pub fn from_gsh(bytes: &[u8]) -> Result<Self, ShaderError> {
+        Self::with_index(bytes, 0)
+    }
+
+    /// Load the vertex and pixel shader at `index` of a GFD file.
+    ///
+    /// The shader programs are copied into GPU memory, so `bytes` doesn't need to outlive the group.
+    pub fn with_index(bytes: &[u8], index: u32) -> Result<Self, ShaderError> {
+        validate(bytes)?;
+
+        let file = bytes.as_ptr() as *mut core::ffi::c_void;
+        let count =
+            unsafe { sys::GFDGetVertexShaderCount(file).min(sys::GFDGetPixelShaderCount(file)) };
+        if index >= count {
+            return Err(ShaderError::NotFound(index));
+        }
+
+        let mut group = sys::WHBGfxShaderGroup::default();
+        let _resource = super::GFX.acquire();
+
+        if unsafe { sys::WHBGfxLoadGFDShaderGroup(&mut group, index, file) } == 0 {
+            return Err(ShaderError::LoadFailed);
+        }
+
+        Ok(Self { group, _resource })
+    }
+
+    /// Load the first vertex and pixel shader of a GFD file on disk.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ShaderError> {
+        let data = fs::read(path)?;
+        Self::from_gsh(&data)
+    }
+
+    #[inline]
+    pub(crate) fn as_inner(&mut self) -> &mut sys::WHBGfxShaderGroup {
+        &mut self.group
+    }
+}
+
+impl Drop for ShaderGroup {
+    fn drop(&mut self) {
+        unsafe {
+            sys::WHBGfxFreeShaderGroup(&mut self.group);
+        }
+    }
+}
+
+/// Check the file and block headers, so the GFD functions never read out of bounds.
+fn validate(data: &[u8]) -> Result<(), ShaderError> {
+    let header = data
+        .get(..HEADER_SIZE)
+        .ok_or(ShaderError::InvalidFormat("truncated file header"))?;
+    if &header[..4] != FILE_MAGIC {
+        return Err(ShaderError::InvalidFormat("invalid magic"));
+    }
+
+    let mut offset = read_u32(header, 4) as usize;
+    if offset < HEADER_SIZE {
+        return Err(ShaderError::InvalidFormat("invalid header size"));
+    }
+
+    loop {
+        let block = data
+            .get(offset..)
+            .and_then(|block| block.get(..HEADER_SIZE))
+            .ok_or(ShaderError::InvalidFormat("truncated block header"))?;
+        if &block[..4] != BLOCK_MAGIC {
+            return Err(ShaderError::InvalidFormat("invalid block magic"));
+        }
+
+        if read_u32(block, 0x10) == BLOCK_END_OF_FILE {
+            return Ok(());
+        }
+
+        let header_size = read_u32(block, 4) as usize;
+        if header_size < HEADER_SIZE {
+            return Err(ShaderError::InvalidFormat("invalid block header size"));
+        }
+
+        offset = offset
+            .checked_add(header_size)
+            .and_then(|offset| offset.checked_add(read_u32(block, 0x14) as usize))
+            .filter(|&offset| offset <= data.len())
+            .ok_or(ShaderError::InvalidFormat("truncated block"))?;
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes(data[offset..offset + 4].try_into().unwrap())
+}
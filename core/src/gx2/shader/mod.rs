@@ -53,53 +53,57 @@
 
 pub mod attribute;
 pub mod buffer;
+mod group;
 pub mod program;
 
 pub(crate) use super::GFX;
 
 pub use attribute::Attribute;
 pub use buffer::Buffer;
+pub use group::{ShaderError, ShaderGroup};
 pub use program::Program;
 
 use super::context::{Context, TvOrDrc};
-use crate::rrc::RrcGuard;
 use attribute::Attributes;
 use wut_sys as sys;
 
 pub struct Shader<A: Attributes> {
-    _group: sys::WHBGfxShaderGroup,
-    _resource: RrcGuard,
+    group: ShaderGroup,
     pub attributes: A,
 }
 
 impl<A: Attributes> Shader<A> {
     pub fn new(index: u32, program: &program::Program) -> Result<Self, ()> {
-        let mut _group = sys::WHBGfxShaderGroup::default();
-        let _resource = super::GFX.acquire();
-
-        if unsafe { sys::WHBGfxLoadGFDShaderGroup(&mut _group, index, program.as_inner()) } == 0 {
-            return Err(());
-        }
+        let group = ShaderGroup::with_index(program.get(), index).map_err(|_| ())?;
+        Self::from_group(group).map_err(|_| ())
+    }
 
-        let attributes = Attributes::new(&mut _group)?;
+    /// Initialize the attributes `A` of the vertex shader in `group`.
+    pub fn from_group(mut group: ShaderGroup) -> Result<Self, ShaderError> {
+        let attributes =
+            Attributes::new(group.as_inner()).map_err(|_| ShaderError::InvalidAttributes)?;
 
-        if unsafe { sys::WHBGfxInitFetchShader(&mut _group) } == 0 {
-            return Err(());
+        if unsafe { sys::WHBGfxInitFetchShader(group.as_inner()) } == 0 {
+            return Err(ShaderError::LoadFailed);
         }
 
-        Ok(Self {
-            _group,
-            _resource,
-            attributes,
-        })
+        Ok(Self { group, attributes })
     }
 
     pub fn render<S: TvOrDrc>(&mut self, _context: &Context<S>) {
+        self.bind();
         unsafe {
-            sys::GX2SetFetchShader(&mut self._group.fetchShader);
-            sys::GX2SetVertexShader(self._group.vertexShader);
-            sys::GX2SetPixelShader(self._group.pixelShader);
             sys::GX2DrawEx(sys::GX2PrimitiveMode::GX2_PRIMITIVE_MODE_TRIANGLES, 3, 0, 1);
         }
     }
+
+    /// Set the fetch, vertex and pixel shader of this group for the following draw calls.
+    pub(crate) fn bind(&mut self) {
+        let group = self.group.as_inner();
+        unsafe {
+            sys::GX2SetFetchShader(&mut group.fetchShader);
+            sys::GX2SetVertexShader(group.vertexShader);
+            sys::GX2SetPixelShader(group.pixelShader);
+        }
+    }
 }
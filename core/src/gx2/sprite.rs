@@ -0,0 +1,322 @@
+//! Batched 2D sprite rendering
+//!
+//! [SpriteBatch] collects textured quads and draws all consecutive quads sharing a texture with a single draw call.
+//!
+//! # Shader
+//!
+//! The batch requires a shader program with the following interface:
+//!
+//! ```glsl
+//! // vertex shader
+//! layout(location = 0) in vec2 aPosition; // normalized device coordinates
+//! layout(location = 1) in vec2 aTexCoord;
+//! layout(location = 2) in vec4 aColor;
+//!
+//! // pixel shader
+//! layout(binding = 0) uniform sampler2D uTexture;
+//! // output: texture(uTexture, vTexCoord) * vColor
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use wut::gx2::{
+//!     RenderContext,
+//!     color::Color,
+//!     shader::Program,
+//!     sprite::{Rect, SpriteBatch},
+//!     texture::Texture,
+//! };
+//!
+//! static PROGRAM: Program = Program::from(include_bytes!("sprite.gsh"));
+//!
+//! let texture = Texture::new(1, 1, &[Color::white()]).unwrap();
+//! let mut batch = SpriteBatch::new(&PROGRAM, 256).unwrap();
+//! let context = RenderContext::new();
+//!
+//! while wut::process::running() {
+//!     let context = context.ready().drc();
+//!
+//!     batch.begin(&context);
+//!     batch.draw(
+//!         &texture,
+//!         Rect::new(10.0, 10.0, 64.0, 64.0),
+//!         Rect::new(0.0, 0.0, 1.0, 1.0),
+//!         Color::red(),
+//!     );
+//!     batch.end();
+//!
+//!     context.finish();
+//! }
+//! ```
+
+use super::{
+    color::Color,
+    context::{Context, TvOrDrc},
+    shader::{
+        Attribute, Buffer, Program, Shader,
+        attribute::{Attributes, Float2, Norm4},
+        buffer::ResourceFlags,
+    },
+    texture::{Filter, Texture},
+};
+use alloc::vec::Vec;
+use wut_sys as sys;
+
+/// Vertices per quad. Quads are drawn as two triangles.
+const QUAD_VERTICES: usize = 6;
+
+/// Axis aligned rectangle in pixels.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Rect {
+    #[inline]
+    pub const fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Corners in the order top left, top right, bottom left, bottom right.
+    fn corners(&self) -> [(f32, f32); 4] {
+        let (left, top) = (self.x, self.y);
+        let (right, bottom) = (self.x + self.width, self.y + self.height);
+        [(left, top), (right, top), (left, bottom), (right, bottom)]
+    }
+}
+
+impl From<(f32, f32, f32, f32)> for Rect {
+    fn from(value: (f32, f32, f32, f32)) -> Self {
+        Self::new(value.0, value.1, value.2, value.3)
+    }
+}
+
+struct SpriteAttributes {
+    position: Attribute<Float2>,
+    tex_coord: Attribute<Float2>,
+    color: Attribute<Norm4>,
+}
+
+impl Attributes for SpriteAttributes {
+    fn new(group: &mut sys::WHBGfxShaderGroup) -> Result<Self, ()> {
+        Ok(Self {
+            position: Attribute::new(group, "aPosition", 0, 0)?,
+            tex_coord: Attribute::new(group, "aTexCoord", 1, 0)?,
+            color: Attribute::new(group, "aColor", 2, 0)?,
+        })
+    }
+}
+
+/// Quads waiting to be drawn.
+#[derive(Default)]
+struct Pending {
+    positions: Vec<Float2>,
+    tex_coords: Vec<Float2>,
+    colors: Vec<Norm4>,
+}
+
+impl Pending {
+    fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    fn clear(&mut self) {
+        self.positions.clear();
+        self.tex_coords.clear();
+        self.colors.clear();
+    }
+}
+
+/// Batches textured quads into GPU vertex buffers.
+///
+/// Between [begin][SpriteBatch::begin] and [end][SpriteBatch::end], quads are collected and drawn whenever the texture changes. Each screen of each frame may contain at most `capacity` quads. Further quads are ignored until the next `begin`.
+///
+/// The vertices of both screens are kept apart, as the GPU only consumes them once the frame is finished. Buffer space is reused from the first `begin` of the next frame on.
+///
+/// Textures must stay alive until the frame has finished rendering.
+pub struct SpriteBatch {
+    shader: Shader<SpriteAttributes>,
+    positions: Buffer<Float2>,
+    tex_coords: Buffer<Float2>,
+    colors: Buffer<Norm4>,
+    sampler: sys::GX2Sampler,
+    pending: Pending,
+    texture: Option<*const sys::GX2Texture>,
+    /// Screen size of the current batch.
+    screen: (f32, f32),
+    /// First unused vertex of the GPU buffers.
+    cursor: usize,
+    /// First vertex of the current batch.
+    start: usize,
+    /// Maximum number of vertices per batch.
+    batch_size: usize,
+    /// Frame in which the buffers were last written.
+    frame: Option<u32>,
+}
+
+impl SpriteBatch {
+    /// Create a batch which can draw up to `capacity` quads per screen and frame.
+    pub fn new(program: &Program, capacity: usize) -> Result<Self, ()> {
+        let batch_size = capacity * QUAD_VERTICES;
+        // one batch for each screen
+        let vertices = batch_size * 2;
+        let flags =
+            ResourceFlags::BindVertexBuffer | ResourceFlags::UsageCPU | ResourceFlags::UsageGPURead;
+
+        let mut s = Self {
+            shader: Shader::new(0, program)?,
+            positions: Buffer::default(vertices, flags)?,
+            tex_coords: Buffer::default(vertices, flags)?,
+            colors: Buffer::default(vertices, flags)?,
+            sampler: sys::GX2Sampler::default(),
+            pending: Pending::default(),
+            texture: None,
+            screen: (0.0, 0.0),
+            cursor: 0,
+            start: 0,
+            batch_size,
+            frame: None,
+        };
+        s.set_filter(Filter::default());
+
+        Ok(s)
+    }
+
+    /// Set the filter used when sprites are scaled.
+    pub fn set_filter(&mut self, filter: Filter) {
+        unsafe {
+            sys::GX2InitSampler(
+                &mut self.sampler,
+                sys::GX2TexClampMode::GX2_TEX_CLAMP_MODE_CLAMP,
+                filter.into(),
+            );
+        }
+    }
+
+    /// Start a new batch on the screen of `context`.
+    ///
+    /// Enables alpha blending and disables depth testing.
+    pub fn begin<S: TvOrDrc>(&mut self, context: &Context<S>) {
+        let (width, height) = context.size();
+        self.screen = (width as f32, height as f32);
+        self.pending.clear();
+        self.texture = None;
+
+        let frame = super::context::frame();
+        if self.frame != Some(frame) {
+            self.frame = Some(frame);
+            self.cursor = 0;
+        }
+        self.start = self.cursor;
+
+        use sys::{GX2BlendCombineMode as C, GX2BlendMode as B};
+        unsafe {
+            sys::GX2SetColorControl(sys::GX2LogicOp::GX2_LOGIC_OP_COPY, 0xFF, 0, 1);
+            sys::GX2SetBlendControl(
+                sys::GX2RenderTarget::GX2_RENDER_TARGET_0,
+                B::GX2_BLEND_MODE_SRC_ALPHA,
+                B::GX2_BLEND_MODE_INV_SRC_ALPHA,
+                C::GX2_BLEND_COMBINE_MODE_ADD,
+                1,
+                B::GX2_BLEND_MODE_SRC_ALPHA,
+                B::GX2_BLEND_MODE_INV_SRC_ALPHA,
+                C::GX2_BLEND_COMBINE_MODE_ADD,
+            );
+            sys::GX2SetDepthOnlyControl(0, 0, sys::GX2CompareFunction::GX2_COMPARE_FUNC_ALWAYS);
+        }
+    }
+
+    /// Draw the `src` area of `texture` into the `dest` area of the screen, multiplied by `tint`.
+    ///
+    /// Both rectangles are in pixels. Use [Color::white] to draw the texture unchanged.
+    pub fn draw(&mut self, texture: &Texture, dest: Rect, src: Rect, tint: Color) {
+        let end = self.cursor + self.pending.len() + QUAD_VERTICES;
+        if end > self.start + self.batch_size || end > self.positions.len() {
+            return;
+        }
+
+        if self.texture != Some(texture.as_raw()) {
+            self.flush();
+            self.texture = Some(texture.as_raw());
+        }
+
+        let (screen_width, screen_height) = self.screen;
+        let positions = dest.corners().map(|(x, y)| {
+            Float2::from((x / screen_width * 2.0 - 1.0, 1.0 - y / screen_height * 2.0))
+        });
+
+        let (texture_width, texture_height) = (texture.width() as f32, texture.height() as f32);
+        let tex_coords = src
+            .corners()
+            .map(|(x, y)| Float2::from((x / texture_width, y / texture_height)));
+
+        let color = Norm4::from((tint.r, tint.g, tint.b, tint.a));
+
+        // two triangles: top left, top right, bottom left and top right, bottom right, bottom left
+        for i in [0, 1, 2, 1, 3, 2] {
+            self.pending.positions.push(positions[i]);
+            self.pending.tex_coords.push(tex_coords[i]);
+            self.pending.colors.push(color);
+        }
+    }
+
+    /// Draw all remaining quads of the batch.
+    pub fn end(&mut self) {
+        self.flush();
+        self.texture = None;
+    }
+
+    /// Upload pending quads and draw them with a single draw call.
+    fn flush(&mut self) {
+        let count = self.pending.len();
+        let Some(texture) = self.texture else {
+            return;
+        };
+        if count == 0 {
+            return;
+        }
+
+        let range = self.cursor..self.cursor + count;
+        if let (Ok(mut positions), Ok(mut tex_coords), Ok(mut colors)) = (
+            self.positions.lock(),
+            self.tex_coords.lock(),
+            self.colors.lock(),
+        ) {
+            positions[range.clone()].copy_from_slice(&self.pending.positions);
+            tex_coords[range.clone()].copy_from_slice(&self.pending.tex_coords);
+            colors[range].copy_from_slice(&self.pending.colors);
+        } else {
+            self.pending.clear();
+            return;
+        }
+
+        let attributes = &mut self.shader.attributes;
+        attributes.position.set_buffer(&mut self.positions);
+        attributes.tex_coord.set_buffer(&mut self.tex_coords);
+        attributes.color.set_buffer(&mut self.colors);
+        self.shader.bind();
+
+        unsafe {
+            sys::GX2SetPixelTexture(texture, 0);
+            sys::GX2SetPixelSampler(&self.sampler, 0);
+            sys::GX2DrawEx(
+                sys::GX2PrimitiveMode::GX2_PRIMITIVE_MODE_TRIANGLES,
+                count as u32,
+                self.cursor as u32,
+                1,
+            );
+        }
+
+        self.cursor += count;
+        self.pending.clear();
+    }
+}
@@ -0,0 +1,172 @@
+//! Textures sampled by shaders
+//!
+//! # Example
+//!
+//! ```
+//! use wut::gx2::{color::Color, texture::Texture};
+//!
+//! // 2x2 checkerboard
+//! let texture = Texture::new(
+//!     2,
+//!     2,
+//!     &[Color::white(), Color::black(), Color::black(), Color::white()],
+//! )
+//! .unwrap();
+//!
+//! // from the SD card
+//! let sprite = Texture::from_file("/vol/external01/wiiu/apps/game/sprite.png").unwrap();
+//! ```
+
+use super::{color::Color, image::Image, shader::buffer::ResourceFlags};
+use crate::{
+    fs::{self, FilesystemError},
+    path::Path,
+    rrc::RrcGuard,
+};
+use flagset::FlagSet;
+use thiserror::Error;
+use wut_sys as sys;
+
+#[derive(Debug, Error)]
+pub enum TextureError {
+    #[error("Failed to read image file")]
+    Io(#[from] FilesystemError),
+    #[error("Image format is not supported")]
+    UnsupportedFormat,
+    #[error("Invalid image: {0}")]
+    InvalidImage(&'static str),
+    #[error("Failed to allocate texture memory")]
+    AllocationFailed,
+}
+
+/// Sampling filter used when a texture is scaled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Filter {
+    /// Nearest neighbour. Best suited for pixel art.
+    Point,
+    /// Bilinear interpolation.
+    #[default]
+    Linear,
+}
+
+impl Into<sys::GX2TexXYFilterMode::Type> for Filter {
+    fn into(self) -> sys::GX2TexXYFilterMode::Type {
+        use sys::GX2TexXYFilterMode as F;
+        match self {
+            Self::Point => F::GX2_TEX_XY_FILTER_MODE_POINT,
+            Self::Linear => F::GX2_TEX_XY_FILTER_MODE_LINEAR,
+        }
+    }
+}
+
+/// A 2D RGBA8 texture in GPU memory.
+pub struct Texture {
+    texture: sys::GX2Texture,
+    _resource: RrcGuard,
+}
+
+impl Texture {
+    /// Create a texture of `width` x `height` pixels.
+    ///
+    /// `pixels` are stored row by row starting at the top left corner. Fails if `pixels` doesn't contain exactly `width * height` colors or if the GPU memory cannot be allocated.
+    pub fn new(width: u32, height: u32, pixels: &[Color]) -> Result<Self, ()> {
+        let count = (width as usize).checked_mul(height as usize).ok_or(())?;
+        if count == 0 || pixels.len() != count {
+            return Err(());
+        }
+
+        let mut texture = sys::GX2Texture::default();
+        let surface = &mut texture.surface;
+        surface.dim = sys::GX2SurfaceDim::GX2_SURFACE_DIM_TEXTURE_2D;
+        surface.width = width;
+        surface.height = height;
+        surface.depth = 1;
+        surface.mipLevels = 1;
+        surface.format = sys::GX2SurfaceFormat::GX2_SURFACE_FORMAT_UNORM_R8_G8_B8_A8;
+        surface.aa = sys::GX2AAMode::GX2_AA_MODE1X;
+        surface.__bindgen_anon_1.use_ = sys::GX2SurfaceUse::GX2_SURFACE_USE_TEXTURE;
+        surface.tileMode = sys::GX2TileMode::GX2_TILE_MODE_LINEAR_ALIGNED;
+        texture.viewNumMips = 1;
+        texture.viewNumSlices = 1;
+        texture.compMap = 0x00010203;
+
+        let _resource = super::GFX.acquire();
+
+        let flags: FlagSet<ResourceFlags> =
+            ResourceFlags::BindTexture | ResourceFlags::UsageCPUWrite | ResourceFlags::UsageGPURead;
+
+        unsafe {
+            sys::GX2CalcSurfaceSizeAndAlignment(&mut texture.surface);
+            sys::GX2InitTextureRegs(&mut texture);
+
+            if sys::GX2RCreateSurface(&mut texture.surface, flags.bits()) == 0 {
+                return Err(());
+            }
+        }
+
+        let mut s = Self { texture, _resource };
+        s.write(pixels);
+
+        Ok(s)
+    }
+
+    /// Load a PNG or TGA image from a file.
+    ///
+    /// Supported are 8 bit grayscale, RGB, RGBA and palette PNGs (requires the `image` feature) as well as uncompressed or RLE compressed 24/32 bit TGAs. Interlaced PNGs are not supported.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, TextureError> {
+        let data = fs::read(path)?;
+        Self::from_bytes(&data)
+    }
+
+    /// Decode a PNG or TGA image from memory. See [Texture::from_file].
+    pub fn from_bytes(data: &[u8]) -> Result<Self, TextureError> {
+        let image = Image::decode(data)?;
+        Self::new(image.width, image.height, &image.pixels)
+            .map_err(|_| TextureError::AllocationFailed)
+    }
+
+    /// Overwrite the texture content. `pixels` must contain exactly `width * height` colors.
+    pub fn write(&mut self, pixels: &[Color]) {
+        let width = self.width() as usize;
+        let pitch = self.texture.surface.pitch as usize;
+        assert_eq!(pixels.len(), width * self.height() as usize);
+
+        unsafe {
+            let image = sys::GX2RLockSurfaceEx(&mut self.texture.surface, 0, 0) as *mut [u8; 4];
+            if image.is_null() {
+                return;
+            }
+
+            for (y, row) in pixels.chunks_exact(width).enumerate() {
+                for (x, color) in row.iter().enumerate() {
+                    *image.add(y * pitch + x) = [color.r, color.g, color.b, color.a];
+                }
+            }
+
+            sys::GX2RUnlockSurfaceEx(&mut self.texture.surface, 0, 0);
+        }
+    }
+
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.texture.surface.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.texture.surface.height
+    }
+
+    #[inline]
+    pub fn as_raw(&self) -> *const sys::GX2Texture {
+        &self.texture
+    }
+}
+
+impl Drop for Texture {
+    fn drop(&mut self) {
+        unsafe {
+            sys::GX2RDestroySurfaceEx(&mut self.texture.surface, 0);
+        }
+    }
+}
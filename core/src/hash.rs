@@ -0,0 +1,95 @@
+//! Generic hashing support and checksums.
+//!
+//! Re-exports [core::hash] and adds a [CRC-32][crc32] checksum, e.g. to verify downloaded files or save data.
+//!
+//! # Example
+//!
+//! ```
+//! use wut::{fs, hash::crc32, net::http};
+//!
+//! let response = http::get("http://example.com/assets/level1.bin")?;
+//! if crc32(&response.body) == EXPECTED_CRC {
+//!     fs::write("/vol/external01/wiiu/apps/example/level1.bin", &response.body)?;
+//! }
+//! ```
+
+pub use core::hash::*;
+
+/// Lookup table for the reflected polynomial `0xEDB88320`.
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB88320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Compute the CRC-32 (IEEE 802.3, as used by zip and PNG) checksum of `data`.
+///
+/// # Example
+///
+/// ```
+/// use wut::hash::crc32;
+///
+/// assert_eq!(crc32(b""), 0x00000000);
+/// assert_eq!(crc32(b"123456789"), 0xCBF43926);
+/// assert_eq!(crc32(b"The quick brown fox jumps over the lazy dog"), 0x414FA339);
+/// ```
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = Crc32::new();
+    crc.update(data);
+    crc.finalize()
+}
+
+/// Incremental CRC-32 checksum. See [crc32].
+///
+/// # Example
+///
+/// ```
+/// use wut::hash::Crc32;
+///
+/// let mut crc = Crc32::new();
+/// crc.update(b"12345");
+/// crc.update(b"6789");
+/// assert_eq!(crc.finalize(), 0xCBF43926);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crc32 {
+    state: u32,
+}
+
+impl Crc32 {
+    pub const fn new() -> Self {
+        Self { state: !0 }
+    }
+
+    /// Add `data` to the checksum.
+    pub fn update(&mut self, data: &[u8]) {
+        self.state = data.iter().fold(self.state, |crc, &byte| {
+            CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+        });
+    }
+
+    /// Checksum of all data added so far.
+    pub fn finalize(&self) -> u32 {
+        !self.state
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
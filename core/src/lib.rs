@@ -2,15 +2,17 @@
 
 extern crate alloc;
 
-#[cfg(feature = "collections")]
 pub mod collections;
 pub mod dynload;
+pub mod encoding;
 pub mod env;
+pub mod ffi;
 pub mod font;
 pub mod foreground;
 pub mod fs;
 pub mod gamepad;
 pub mod gx2;
+pub mod hash;
 pub mod logger;
 pub mod net;
 pub mod path;
@@ -19,6 +21,7 @@ pub mod ptr;
 pub mod rrc;
 pub mod screen;
 pub mod sync;
+pub mod test;
 pub mod thread;
 pub mod time;
 pub mod title;
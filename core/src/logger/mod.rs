@@ -1,10 +1,21 @@
 //! Logging interface over various channels.
 //!
 //! This module provides a unified logging interface for the Wii U, allowing for logging to multiple channels such as the Cafe logging system, console output, WUMS logging module, and UDP broadcasting.
+//!
+//! With the `panic_handler` feature, panic messages are written to all initialized channels before the panic screen is shown.
 
-use crate::sync::{ConstMutex, LazyLock, Mutex, MutexError};
+use crate::{
+    sync::{ConstMutex, LazyLock, Mutex, MutexError},
+    time::{DateTime, Duration, Instant},
+};
 pub use Channel::{Cafe, Console, Module, Udp};
-use alloc::ffi::{CString, NulError};
+use alloc::{
+    collections::BTreeMap,
+    ffi::{CString, NulError},
+    format,
+    string::String,
+};
+use core::fmt;
 use flagset::{FlagSet, flags};
 use thiserror::Error;
 use wut_sys as sys;
@@ -12,16 +23,27 @@ use wut_sys as sys;
 #[derive(Debug)]
 struct Logger {
     channels: FlagSet<Channel>,
+    level: Level,
+    timestamps: bool,
     counter: u32,
 }
 
 static LOGGER: ConstMutex<Logger> = LazyLock::new(|| {
     Mutex::new(Logger {
         channels: FlagSet::new_truncated(0),
+        level: Level::Trace,
+        timestamps: false,
         counter: 0,
     })
 });
 
+/// Time each key of [print_throttled] was last printed.
+static THROTTLED: ConstMutex<BTreeMap<String, Instant>> =
+    LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
+/// Background color of the console channel.
+const CONSOLE_COLOR: u32 = 0xFF000000;
+
 flags! {
     /// Logging channels for the Wii U.
     pub enum Channel: u8 {
@@ -36,6 +58,40 @@ flags! {
     }
 }
 
+/// Severity of a log message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Level {
+    #[default]
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    /// Background color of the console channel for this level.
+    fn console_color(&self) -> u32 {
+        match self {
+            Level::Trace | Level::Debug | Level::Info => CONSOLE_COLOR,
+            Level::Warn => 0x80600000,
+            Level::Error => 0x80000000,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Level::Trace => "TRACE",
+            Level::Debug => "DEBUG",
+            Level::Info => "INFO",
+            Level::Warn => "WARN",
+            Level::Error => "ERROR",
+        })
+    }
+}
+
 /// Errors that can occur during logging operations.
 #[derive(Debug, Error)]
 pub enum LoggerError {
@@ -79,7 +135,7 @@ pub fn init(channels: impl Into<FlagSet<Channel>>) -> Result<(), LoggerError> {
             if sys::WHBLogConsoleInit() != 0 {
                 return Err(LoggerError::ConsoleFailed);
             } else {
-                sys::WHBLogConsoleSetColor(0xFF000000);
+                sys::WHBLogConsoleSetColor(CONSOLE_COLOR);
             }
         }
         if new.contains(Channel::Module) && sys::WHBLogModuleInit() == 0 {
@@ -151,6 +207,32 @@ pub fn deinit() {
     logger.counter = 0;
 }
 
+/// Set the minimum [Level] of messages which are logged.
+///
+/// Messages with a lower level are discarded. Defaults to [Level::Trace].
+pub fn set_level(level: Level) -> Result<(), LoggerError> {
+    LOGGER.lock()?.level = level;
+    Ok(())
+}
+
+/// Get the minimum [Level] of messages which are logged.
+pub fn level() -> Result<Level, LoggerError> {
+    Ok(LOGGER.lock()?.level)
+}
+
+/// Enable or disable prefixing every message with the current time.
+///
+/// The timestamp has the format `[HH:MM:SS.mmm]`. Disabled by default.
+pub fn enable_timestamps(enable: bool) -> Result<(), LoggerError> {
+    LOGGER.lock()?.timestamps = enable;
+    Ok(())
+}
+
+/// Check if messages with `level` are logged.
+pub fn enabled(level: Level) -> bool {
+    LOGGER.lock().map_or(false, |logger| level >= logger.level)
+}
+
 /// Print content to the logger.
 ///
 /// # Errors
@@ -158,11 +240,88 @@ pub fn deinit() {
 /// Returns an error if the logger is not initialized or if the provided string contains internal null bytes.
 pub fn print(text: &str) -> Result<(), LoggerError> {
     let logger = LOGGER.lock()?;
+    write(&logger, text)
+}
+
+/// Print content to the logger, unless a message with the same `key` was printed less than `min_interval` ago.
+///
+/// Dropped messages are not queued. Useful for status messages in loops which run every frame.
+///
+/// # Errors
+///
+/// Returns an error if the logger is not initialized or if the provided string contains internal null bytes.
+///
+/// # Example
+///
+/// ```
+/// use core::time::Duration;
+/// use wut::logger;
+///
+/// while wut::process::running() {
+///     // printed at most once per second
+///     let _ = logger::print_throttled("position", Duration::from_secs(1), &format!("{position:?}"));
+/// }
+/// ```
+pub fn print_throttled(key: &str, min_interval: Duration, text: &str) -> Result<(), LoggerError> {
+    {
+        let mut throttled = THROTTLED.lock()?;
+        match throttled.get_mut(key) {
+            Some(last) if last.elapsed() < min_interval => return Ok(()),
+            Some(last) => *last = Instant::now(),
+            None => {
+                throttled.insert(String::from(key), Instant::now());
+            }
+        }
+    }
+
+    print(text)
+}
+
+/// Print content with a [Level] prefix to the logger.
+///
+/// Content is discarded if `level` is below the [configured level][set_level].
+///
+/// # Errors
+///
+/// Returns an error if the logger is not initialized or if the provided string contains internal null bytes.
+pub fn log(level: Level, text: &str) -> Result<(), LoggerError> {
+    let logger = LOGGER.lock()?;
+
+    if level < logger.level {
+        return Ok(());
+    }
+
+    let console = logger.channels.contains(Channel::Console);
+    if console {
+        unsafe {
+            sys::WHBLogConsoleSetColor(level.console_color());
+        }
+    }
+
+    let result = write(&logger, &format!("[{level}] {text}"));
 
+    if console {
+        unsafe {
+            sys::WHBLogConsoleSetColor(CONSOLE_COLOR);
+        }
+    }
+
+    result
+}
+
+fn write(logger: &Logger, text: &str) -> Result<(), LoggerError> {
     if logger.channels.is_empty() {
         Err(LoggerError::Uninitialized)
     } else {
-        let text = CString::new(text)?;
+        let text = if logger.timestamps {
+            let now = DateTime::now();
+            CString::new(format!(
+                "[{:02}:{:02}:{:02}.{:03}] {text}",
+                now.hour, now.min, now.sec, now.millisec
+            ))?
+        } else {
+            CString::new(text)?
+        };
         unsafe {
             sys::WHBLogPrint(text.as_ptr());
 
@@ -193,3 +352,94 @@ macro_rules! println {
         let _ = $crate::logger::print(&format(format_args!($($arg)*))).unwrap();
     }};
 }
+
+/// Prints to the logger output, but only the first time this invocation is reached.
+///
+/// Built on [print_throttled][crate::logger::print_throttled], keyed by the location of the invocation.
+///
+/// # Panics
+///
+/// Panics if no logger is current initialized or if logger is currently locked elsewhere.
+///
+/// # Example
+///
+/// ```
+/// while wut::process::running() {
+///     wut::log_once!("entered main loop");
+/// }
+/// ```
+#[macro_export]
+macro_rules! log_once {
+    ($($arg:tt)*) => {{
+        extern crate alloc;
+        use alloc::fmt::format;
+
+        let key = concat!(file!(), ":", line!(), ":", column!());
+        let _ = $crate::logger::print_throttled(
+            key,
+            ::core::time::Duration::MAX,
+            &format(format_args!($($arg)*)),
+        )
+        .unwrap();
+    }};
+}
+
+/// Logs a message at the specified level.
+///
+/// The message is only formatted if the level is [enabled][crate::logger::enabled].
+///
+/// # Panics
+///
+/// Panics if no logger is current initialized or if logger is currently locked elsewhere.
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {{
+        extern crate alloc;
+        use alloc::fmt::format;
+
+        let level = $level;
+        if $crate::logger::enabled(level) {
+            let _ = $crate::logger::log(level, &format(format_args!($($arg)+))).unwrap();
+        }
+    }};
+}
+
+/// Logs a message at the [Trace][crate::logger::Level::Trace] level.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => {
+        $crate::log!($crate::logger::Level::Trace, $($arg)+)
+    };
+}
+
+/// Logs a message at the [Debug][crate::logger::Level::Debug] level.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        $crate::log!($crate::logger::Level::Debug, $($arg)+)
+    };
+}
+
+/// Logs a message at the [Info][crate::logger::Level::Info] level.
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => {
+        $crate::log!($crate::logger::Level::Info, $($arg)+)
+    };
+}
+
+/// Logs a message at the [Warn][crate::logger::Level::Warn] level.
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => {
+        $crate::log!($crate::logger::Level::Warn, $($arg)+)
+    };
+}
+
+/// Logs a message at the [Error][crate::logger::Level::Error] level.
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => {
+        $crate::log!($crate::logger::Level::Error, $($arg)+)
+    };
+}
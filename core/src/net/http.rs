@@ -0,0 +1,187 @@
+//! Minimal HTTP/1.1 client
+//!
+//! Only plain `http://` URLs are supported. HTTPS requires a TLS implementation and is not available yet, so `https://` URLs fail with [HttpError::UnsupportedScheme].
+//!
+//! # Example
+//!
+//! ```
+//! use wut::net::http;
+//!
+//! let response = http::get("http://example.com/index.html")?;
+//! if response.status == 200 {
+//!     println!("{}", String::from_utf8_lossy(&response.body));
+//! }
+//! ```
+
+use crate::net::{socket::SocketError, tcp::TcpStream};
+use alloc::{format, string::String, vec::Vec};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum HttpError {
+    #[error("Connection failed")]
+    Socket(#[from] SocketError),
+    #[error("Invalid URL, expected `http://host[:port][/path]`")]
+    InvalidUrl,
+    #[error("Only `http://` URLs are supported")]
+    UnsupportedScheme,
+    #[error("Malformed response: {0}")]
+    InvalidResponse(&'static str),
+}
+
+/// Response to a HTTP request.
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    /// Header names and values in the order they were received.
+    pub headers: Vec<(String, String)>,
+    /// Body with any transfer encoding removed.
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Value of the first header called `name`. Names are compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Send a `GET` request to `url` and wait for the complete response.
+///
+/// Redirects are not followed. The connection is closed after the response has been received.
+pub fn get(url: &str) -> Result<HttpResponse, HttpError> {
+    let (host, port, path) = parse_url(url)?;
+
+    let mut stream = TcpStream::connect((host, port))?;
+
+    let authority = if port == 80 {
+        String::from(host)
+    } else {
+        format!("{host}:{port}")
+    };
+    let request = format!(
+        "GET {path} HTTP/1.1\r\nHost: {authority}\r\nUser-Agent: wut\r\nAccept: */*\r\nConnection: close\r\n\r\n"
+    );
+    stream.write_all(request.as_bytes())?;
+
+    let mut data = Vec::new();
+    let mut buffer = [0u8; 4096];
+    loop {
+        match stream.read(&mut buffer) {
+            Ok(n) => data.extend_from_slice(&buffer[..n]),
+            Err(SocketError::ConnectionClosed) => break,
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    parse_response(&data)
+}
+
+/// Split `url` into host, port and path.
+fn parse_url(url: &str) -> Result<(&str, u16, &str), HttpError> {
+    let (scheme, rest) = url.split_once("://").ok_or(HttpError::InvalidUrl)?;
+    if !scheme.eq_ignore_ascii_case("http") {
+        return Err(HttpError::UnsupportedScheme);
+    }
+
+    let (authority, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (host, port.parse().map_err(|_| HttpError::InvalidUrl)?),
+        None => (authority, 80),
+    };
+
+    if host.is_empty() {
+        return Err(HttpError::InvalidUrl);
+    }
+
+    Ok((host, port, path))
+}
+
+fn parse_response(data: &[u8]) -> Result<HttpResponse, HttpError> {
+    let end = find(data, b"\r\n\r\n").ok_or(HttpError::InvalidResponse("incomplete header"))?;
+    let head = core::str::from_utf8(&data[..end])
+        .map_err(|_| HttpError::InvalidResponse("header is not valid UTF-8"))?;
+    let body = &data[end + 4..];
+
+    let mut lines = head.split("\r\n");
+    let status = lines
+        .next()
+        .and_then(|line| {
+            let mut parts = line.splitn(3, ' ');
+            parts.next().filter(|v| v.starts_with("HTTP/"))?;
+            parts.next()?.parse::<u16>().ok()
+        })
+        .ok_or(HttpError::InvalidResponse("invalid status line"))?;
+
+    let headers = lines
+        .map(|line| {
+            line.split_once(':')
+                .map(|(key, value)| (String::from(key.trim()), String::from(value.trim())))
+                .ok_or(HttpError::InvalidResponse("invalid header"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut response = HttpResponse {
+        status,
+        headers,
+        body: Vec::new(),
+    };
+
+    response.body = if response
+        .header("Transfer-Encoding")
+        .is_some_and(|v| v.to_ascii_lowercase().contains("chunked"))
+    {
+        decode_chunked(body)?
+    } else if let Some(length) = response.header("Content-Length") {
+        let length = length
+            .parse::<usize>()
+            .map_err(|_| HttpError::InvalidResponse("invalid content length"))?;
+        body.get(..length)
+            .ok_or(HttpError::InvalidResponse("truncated body"))?
+            .to_vec()
+    } else {
+        // body ends when the connection is closed
+        body.to_vec()
+    };
+
+    Ok(response)
+}
+
+/// Remove the chunked transfer encoding. Trailers are ignored.
+fn decode_chunked(mut data: &[u8]) -> Result<Vec<u8>, HttpError> {
+    let mut body = Vec::new();
+
+    loop {
+        let line_end = find(data, b"\r\n").ok_or(HttpError::InvalidResponse("truncated chunk"))?;
+        let size = core::str::from_utf8(&data[..line_end])
+            .ok()
+            // ignore chunk extensions
+            .and_then(|line| line.split(';').next())
+            .and_then(|size| usize::from_str_radix(size.trim(), 16).ok())
+            .ok_or(HttpError::InvalidResponse("invalid chunk size"))?;
+        data = &data[line_end + 2..];
+
+        if size == 0 {
+            return Ok(body);
+        }
+
+        let chunk = data
+            .get(..size)
+            .ok_or(HttpError::InvalidResponse("truncated chunk"))?;
+        body.extend_from_slice(chunk);
+        data = data.get(size + 2..).unwrap_or_default();
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
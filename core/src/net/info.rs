@@ -0,0 +1,79 @@
+// info
+
+use crate::net::{NET, socket::SocketError};
+use core::net::Ipv4Addr;
+use wut_sys as sys;
+
+/// IPv4 configuration of the console.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkInfo {
+    /// Whether the console is connected to a network.
+    pub connected: bool,
+    /// Assigned address. [Ipv4Addr::UNSPECIFIED] if not connected.
+    pub ip: Ipv4Addr,
+    /// Assigned subnet mask. [Ipv4Addr::UNSPECIFIED] if not connected.
+    pub subnet_mask: Ipv4Addr,
+}
+
+impl NetworkInfo {
+    /// Broadcast address of the local network, e.g. for discovery via [UdpSocket][super::UdpSocket].
+    pub fn broadcast(&self) -> Ipv4Addr {
+        Ipv4Addr::from_bits(self.ip.to_bits() | !self.subnet_mask.to_bits())
+    }
+}
+
+#[inline]
+fn success(result: sys::NNResult) -> bool {
+    result.value >= 0
+}
+
+/// The IPv4 address currently assigned to the console.
+///
+/// # Example
+///
+/// ```
+/// use wut::net;
+///
+/// if let Ok(ip) = net::local_ip() {
+///     println!("connect to {ip}:8080");
+/// }
+/// ```
+pub fn local_ip() -> Result<Ipv4Addr, SocketError> {
+    let _resource = NET.acquire();
+
+    let mut ip = 0;
+    if success(unsafe { sys::ACGetAssignedAddress(&mut ip) }) && ip != 0 {
+        Ok(Ipv4Addr::from_bits(ip))
+    } else {
+        Err(SocketError::NetworkUnavailable)
+    }
+}
+
+/// Query the current network configuration of the console.
+///
+/// The AC library does not expose the default gateway, so it is not part of [NetworkInfo].
+pub fn network_info() -> NetworkInfo {
+    let _resource = NET.acquire();
+
+    let mut connected = 0;
+    let mut ip = 0;
+    let mut subnet = 0;
+
+    unsafe {
+        if !success(sys::ACIsApplicationConnected(&mut connected)) {
+            connected = 0;
+        }
+        if connected == 0 || !success(sys::ACGetAssignedAddress(&mut ip)) {
+            ip = 0;
+        }
+        if connected == 0 || !success(sys::ACGetAssignedSubnet(&mut subnet)) {
+            subnet = 0;
+        }
+    }
+
+    NetworkInfo {
+        connected: connected != 0,
+        ip: Ipv4Addr::from_bits(ip),
+        subnet_mask: Ipv4Addr::from_bits(subnet),
+    }
+}
@@ -3,10 +3,45 @@
 //! This module provides networking functionality for the Transmission Control and User Datagram Protocols, as well as types for IP and socket addresses.
 
 pub mod errno;
+pub mod http;
+mod info;
 pub mod socket;
 mod socket_addrs;
 pub mod tcp;
+pub mod udp;
 
 pub use core::net::*;
+pub use info::{NetworkInfo, local_ip, network_info};
+pub use socket::{Shutdown, SocketError};
 pub use socket_addrs::{ToSocketAddrs, ToSocketAddrsError};
 pub use tcp::{TcpListener, TcpStream};
+pub use udp::UdpSocket;
+
+use crate::rrc::Rrc;
+use wut_sys as sys;
+
+/// Network connection of the console.
+///
+/// Every socket, [DNS lookup][ToSocketAddrs] and [network_info] call holds a guard, so the connection is established on first use and closed once the last user is gone. The socket library itself is initialized once by the WUT runtime and must not be initialized again.
+///
+/// Holding a guard keeps the connection alive between short-lived sockets, e.g. for repeated [http::get] requests.
+///
+/// # Example
+///
+/// ```
+/// let _network = wut::net::NET.acquire();
+///
+/// for url in ["http://example.com/a", "http://example.com/b"] {
+///     let response = wut::net::http::get(url)?;
+/// }
+/// ```
+pub static NET: Rrc = Rrc::new(
+    || unsafe {
+        sys::ACInitialize();
+        sys::ACConnect();
+    },
+    || unsafe {
+        sys::ACClose();
+        sys::ACFinalize();
+    },
+);
@@ -1,16 +1,25 @@
 // socket
 
-use crate::net::{
-    errno,
-    socket_addrs::{ToSocketAddrs, ToSocketAddrsError},
+use crate::{
+    net::{
+        NET, errno,
+        socket_addrs::{ToSocketAddrs, ToSocketAddrsError},
+    },
+    rrc::RrcGuard,
+};
+use core::{
+    ffi, fmt,
+    net::{Ipv4Addr, SocketAddrV4},
+    time::Duration,
 };
-use core::net::{Ipv4Addr, SocketAddrV4};
 use wut_sys as sys;
 // use flagset::{flags, FlagSet};
 use thiserror::Error;
 
-#[derive(Debug)]
-pub struct Socket(i32);
+pub struct Socket {
+    fd: i32,
+    _resource: RrcGuard,
+}
 
 #[derive(Debug, Error)]
 pub enum SocketError {
@@ -22,12 +31,16 @@ pub enum SocketError {
     InvalidAddress(#[from] ToSocketAddrsError),
     #[error("failed to bind to address")]
     NoAvailableAddress,
+    #[error("failed to connect to address")]
+    CannotConnect,
     #[error("failed to listen on socket")]
     CannotListenOnSocket,
     #[error("Connection closed by either of the parties")]
     ConnectionClosed,
     #[error("failed to accept incoming request")]
     CannotAccept,
+    #[error("the console is not connected to a network")]
+    NetworkUnavailable,
     #[error("A system-level error occurred")]
     SystemError(#[from] errno::SystemError),
 }
@@ -53,6 +66,7 @@ pub enum Shutdown {
 
 impl Socket {
     pub fn tcp() -> Result<Self, SocketError> {
+        let resource = NET.acquire();
         unsafe {
             let fd = sys::socket(
                 sys::AF_INET as i32,
@@ -72,12 +86,16 @@ impl Socket {
                 //     core::mem::size_of::<i32>() as u32,
                 // );
 
-                Ok(Self(fd))
+                Ok(Self {
+                    fd,
+                    _resource: resource,
+                })
             }
         }
     }
 
     pub fn udp() -> Result<Self, SocketError> {
+        let resource = NET.acquire();
         unsafe {
             let fd = sys::socket(
                 sys::AF_INET as i32,
@@ -87,25 +105,25 @@ impl Socket {
             if fd <= 0 {
                 Err(SocketError::UdpCreation)
             } else {
-                Ok(Self(fd))
+                Ok(Self {
+                    fd,
+                    _resource: resource,
+                })
             }
         }
     }
 
     pub fn valid(&self) -> bool {
-        self.0 > 0
+        self.fd > 0
     }
 
     pub fn bind(&self, addr: impl ToSocketAddrs) -> Result<SocketAddrV4, SocketError> {
         for address in addr.to_socket_addrs()? {
-            let mut addr = sys::sockaddr_in::default();
-            addr.sin_family = sys::AF_INET as u16;
-            addr.sin_addr.s_addr = address.ip().to_bits();
-            addr.sin_port = address.port();
+            let addr = sockaddr(address);
 
             let status = unsafe {
                 sys::bind(
-                    self.0,
+                    self.fd,
                     &addr as *const _ as *const sys::sockaddr,
                     core::mem::size_of::<sys::sockaddr_in>() as u32,
                 )
@@ -119,8 +137,29 @@ impl Socket {
         Err(SocketError::NoAvailableAddress)
     }
 
+    /// Connect to the first address of `addr` which accepts the connection.
+    pub fn connect(&self, addr: impl ToSocketAddrs) -> Result<SocketAddrV4, SocketError> {
+        for address in addr.to_socket_addrs()? {
+            let addr = sockaddr(address);
+
+            let status = unsafe {
+                sys::connect(
+                    self.fd,
+                    &addr as *const _ as *const sys::sockaddr,
+                    core::mem::size_of::<sys::sockaddr_in>() as u32,
+                )
+            };
+
+            if status == 0 {
+                return Ok(address);
+            }
+        }
+
+        Err(SocketError::CannotConnect)
+    }
+
     pub fn listen(&self, backlog: u32) -> Result<(), SocketError> {
-        if unsafe { sys::listen(self.0, backlog as i32) } == 0 {
+        if unsafe { sys::listen(self.fd, backlog as i32) } == 0 {
             Ok(())
         } else {
             Err(SocketError::CannotListenOnSocket)
@@ -132,23 +171,41 @@ impl Socket {
         let mut len = size_of::<sys::sockaddr_in>() as u32;
 
         let fd =
-            unsafe { sys::accept(self.0, &mut addr as *mut _ as *mut sys::sockaddr, &mut len) };
+            unsafe { sys::accept(self.fd, &mut addr as *mut _ as *mut sys::sockaddr, &mut len) };
 
         if fd < 0 {
             Err(SocketError::from_errno())
         } else if fd == 0 {
             Err(SocketError::CannotAccept)
         } else {
-            let ip = Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr));
-            let port = u16::from_be(addr.sin_port);
-            let socket_addr = SocketAddrV4::new(ip, port);
+            Ok((
+                Socket {
+                    fd,
+                    _resource: NET.acquire(),
+                },
+                socket_addr(&addr),
+            ))
+        }
+    }
 
-            Ok((Socket(fd), socket_addr))
+    /// Address the socket is bound to. Unlike the address passed to [Socket::bind], this contains the actual port if port `0` was requested.
+    pub fn local_addr(&self) -> Result<SocketAddrV4, SocketError> {
+        let mut addr = sys::sockaddr_in::default();
+        let mut len = size_of::<sys::sockaddr_in>() as u32;
+
+        let status = unsafe {
+            sys::getsockname(self.fd, &mut addr as *mut _ as *mut sys::sockaddr, &mut len)
+        };
+
+        if status < 0 {
+            Err(SocketError::from_errno())
+        } else {
+            Ok(socket_addr(&addr))
         }
     }
 
     pub fn read(&self, buf: &mut [u8]) -> Result<usize, SocketError> {
-        let bytes = unsafe { sys::recv(self.0, buf.as_mut_ptr() as *mut _, buf.len(), 0) };
+        let bytes = unsafe { sys::recv(self.fd, buf.as_mut_ptr() as *mut _, buf.len(), 0) };
 
         if bytes < 0 {
             Err(SocketError::from_errno())
@@ -160,7 +217,7 @@ impl Socket {
     }
 
     pub fn write(&mut self, buf: &[u8]) -> Result<usize, SocketError> {
-        let bytes = unsafe { sys::send(self.0, buf.as_ptr() as *const _, buf.len(), 0) };
+        let bytes = unsafe { sys::send(self.fd, buf.as_ptr() as *const _, buf.len(), 0) };
 
         if bytes < 0 {
             Err(SocketError::from_errno())
@@ -178,13 +235,134 @@ impl Socket {
             Shutdown::Both => sys::SHUT_RDWR,
         } as i32;
 
-        let success = unsafe { sys::shutdown(self.0, how) };
+        let success = unsafe { sys::shutdown(self.fd, how) };
         if success < 0 {
             Err(SocketError::from_errno())
         } else {
             Ok(())
         }
     }
+
+    /// Send a datagram to `address`.
+    pub fn send_to(&self, buf: &[u8], address: SocketAddrV4) -> Result<usize, SocketError> {
+        let addr = sockaddr(address);
+        let bytes = unsafe {
+            sys::sendto(
+                self.fd,
+                buf.as_ptr() as *const _,
+                buf.len(),
+                0,
+                &addr as *const _ as *const sys::sockaddr,
+                core::mem::size_of::<sys::sockaddr_in>() as u32,
+            )
+        };
+
+        if bytes < 0 {
+            Err(SocketError::from_errno())
+        } else {
+            Ok(bytes as usize)
+        }
+    }
+
+    /// Receive a datagram. Empty datagrams are valid and reported as `0` bytes.
+    pub fn recv_from(&self, buf: &mut [u8]) -> Result<(usize, SocketAddrV4), SocketError> {
+        let mut addr = sys::sockaddr_in::default();
+        let mut len = size_of::<sys::sockaddr_in>() as u32;
+
+        let bytes = unsafe {
+            sys::recvfrom(
+                self.fd,
+                buf.as_mut_ptr() as *mut _,
+                buf.len(),
+                0,
+                &mut addr as *mut _ as *mut sys::sockaddr,
+                &mut len,
+            )
+        };
+
+        if bytes < 0 {
+            Err(SocketError::from_errno())
+        } else {
+            let ip = Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr));
+            let port = u16::from_be(addr.sin_port);
+            Ok((bytes as usize, SocketAddrV4::new(ip, port)))
+        }
+    }
+
+    /// Send on a connected socket. Unlike [Socket::write], sending `0` bytes is not an error.
+    pub fn send(&self, buf: &[u8]) -> Result<usize, SocketError> {
+        let bytes = unsafe { sys::send(self.fd, buf.as_ptr() as *const _, buf.len(), 0) };
+
+        if bytes < 0 {
+            Err(SocketError::from_errno())
+        } else {
+            Ok(bytes as usize)
+        }
+    }
+
+    /// Receive on a connected socket. Unlike [Socket::read], receiving `0` bytes is not an error.
+    pub fn recv(&self, buf: &mut [u8]) -> Result<usize, SocketError> {
+        let bytes = unsafe { sys::recv(self.fd, buf.as_mut_ptr() as *mut _, buf.len(), 0) };
+
+        if bytes < 0 {
+            Err(SocketError::from_errno())
+        } else {
+            Ok(bytes as usize)
+        }
+    }
+
+    /// In non-blocking mode, operations which would block fail with [errno::SystemError::NoMoreProcesses] (`EAGAIN`) instead.
+    pub fn set_nonblocking(&self, nonblocking: bool) -> Result<(), SocketError> {
+        self.set_option(sys::SOL_SOCKET, sys::SO_NONBLOCK, &(nonblocking as i32))
+    }
+
+    pub fn set_option<T>(&self, level: u32, option: u32, value: &T) -> Result<(), SocketError> {
+        let status = unsafe {
+            sys::setsockopt(
+                self.fd,
+                level as i32,
+                option as i32,
+                value as *const T as *const ffi::c_void,
+                core::mem::size_of::<T>() as u32,
+            )
+        };
+
+        if status < 0 {
+            Err(SocketError::from_errno())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Set a receive (`SO_RCVTIMEO`) or send (`SO_SNDTIMEO`) timeout. `None` blocks indefinitely.
+    pub fn set_timeout(&self, option: u32, timeout: Option<Duration>) -> Result<(), SocketError> {
+        let mut value = sys::timeval::default();
+        if let Some(timeout) = timeout {
+            value.tv_sec = timeout.as_secs() as _;
+            value.tv_usec = timeout.subsec_micros() as _;
+        }
+        self.set_option(sys::SOL_SOCKET, option, &value)
+    }
+}
+
+impl fmt::Debug for Socket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Socket").field(&self.fd).finish()
+    }
+}
+
+fn sockaddr(address: SocketAddrV4) -> sys::sockaddr_in {
+    let mut addr = sys::sockaddr_in::default();
+    addr.sin_family = sys::AF_INET as u16;
+    addr.sin_addr.s_addr = address.ip().to_bits().to_be();
+    addr.sin_port = address.port().to_be();
+    addr
+}
+
+fn socket_addr(addr: &sys::sockaddr_in) -> SocketAddrV4 {
+    let ip = Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr));
+    let port = u16::from_be(addr.sin_port);
+    SocketAddrV4::new(ip, port)
 }
 
 impl Drop for Socket {
@@ -196,8 +374,8 @@ impl Drop for Socket {
             // let s = sys::shutdown(self.0, sys::SHUT_RDWR as i32);
             // crate::println!("shutdown: {s}");
             // crate::thread::sleep(crate::time::Duration::from_secs(2));
-            sys::close(self.0);
+            sys::close(self.fd);
         }
-        self.0 = -1;
+        self.fd = -1;
     }
 }
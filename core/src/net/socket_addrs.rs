@@ -2,9 +2,11 @@
 //!
 //! Conversion from common representations to addresses.
 
-use alloc::{string::String, vec};
-use core::{iter, net::*, option, slice};
+use crate::net::NET;
+use alloc::{ffi::CString, string::String, vec, vec::Vec};
+use core::{iter, net::*, option, ptr, slice};
 use thiserror::Error;
+use wut_sys as sys;
 
 #[derive(Debug, Error)]
 pub enum ToSocketAddrsError {
@@ -12,6 +14,52 @@ pub enum ToSocketAddrsError {
     AddressParse(#[from] AddrParseError),
     #[error("failure when resolving address")]
     CannotResolveHostname,
+    #[error("invalid socket address, expected `host:port`")]
+    InvalidSocketAddress,
+    #[error("invalid port value")]
+    InvalidPort,
+    #[error("IPv6 addresses are not supported")]
+    Ipv6Unsupported,
+}
+
+/// Resolve `host` to all of its IPv4 addresses using the DNS resolver of the network stack.
+fn resolve(host: &str, port: u16) -> Result<vec::IntoIter<SocketAddrV4>, ToSocketAddrsError> {
+    let _resource = NET.acquire();
+
+    let node = CString::new(host).map_err(|_| ToSocketAddrsError::CannotResolveHostname)?;
+    let mut hints = sys::addrinfo::default();
+    hints.ai_family = sys::AF_INET as i32;
+    hints.ai_socktype = sys::SOCK_STREAM as i32;
+
+    let mut result: *mut sys::addrinfo = ptr::null_mut();
+    let status = unsafe { sys::getaddrinfo(node.as_ptr(), ptr::null(), &hints, &mut result) };
+    if status != 0 || result.is_null() {
+        return Err(ToSocketAddrsError::CannotResolveHostname);
+    }
+
+    let mut addrs = Vec::new();
+    let mut info = result;
+    while !info.is_null() {
+        unsafe {
+            let addr = (*info).ai_addr;
+            if !addr.is_null() && (*info).ai_family == sys::AF_INET as i32 {
+                let addr = &*(addr as *const sys::sockaddr_in);
+                let ip = Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr));
+                addrs.push(SocketAddrV4::new(ip, port));
+            }
+            info = (*info).ai_next;
+        }
+    }
+
+    unsafe {
+        sys::freeaddrinfo(result);
+    }
+
+    if addrs.is_empty() {
+        Err(ToSocketAddrsError::CannotResolveHostname)
+    } else {
+        Ok(addrs.into_iter())
+    }
 }
 
 pub trait ToSocketAddrs {
@@ -26,6 +74,16 @@ impl ToSocketAddrs for SocketAddrV4 {
     }
 }
 
+impl ToSocketAddrs for SocketAddr {
+    type Iter = option::IntoIter<SocketAddrV4>;
+    fn to_socket_addrs(&self) -> Result<Self::Iter, ToSocketAddrsError> {
+        match self {
+            SocketAddr::V4(addr) => Ok(Some(*addr).into_iter()),
+            SocketAddr::V6(_) => Err(ToSocketAddrsError::Ipv6Unsupported),
+        }
+    }
+}
+
 impl ToSocketAddrs for (Ipv4Addr, u16) {
     type Iter = option::IntoIter<SocketAddrV4>;
     fn to_socket_addrs(&self) -> Result<Self::Iter, ToSocketAddrsError> {
@@ -40,7 +98,7 @@ impl ToSocketAddrs for (&str, u16) {
         let (host, port) = *self;
         match host.parse::<Ipv4Addr>() {
             Ok(addr) => Ok(vec![SocketAddrV4::new(addr, port)].into_iter()),
-            Err(_) => Err(ToSocketAddrsError::CannotResolveHostname),
+            Err(_) => resolve(host, port),
         }
     }
 }
@@ -55,10 +113,18 @@ impl ToSocketAddrs for (String, u16) {
 impl ToSocketAddrs for str {
     type Iter = vec::IntoIter<SocketAddrV4>;
     fn to_socket_addrs(&self) -> Result<Self::Iter, ToSocketAddrsError> {
-        match self.parse::<SocketAddrV4>() {
-            Ok(addr) => Ok(vec![addr].into_iter()),
-            Err(_) => Err(ToSocketAddrsError::CannotResolveHostname),
+        if let Ok(addr) = self.parse::<SocketAddrV4>() {
+            return Ok(vec![addr].into_iter());
         }
+
+        let (host, port) = self
+            .rsplit_once(':')
+            .ok_or(ToSocketAddrsError::InvalidSocketAddress)?;
+        let port = port
+            .parse::<u16>()
+            .map_err(|_| ToSocketAddrsError::InvalidPort)?;
+
+        (host, port).to_socket_addrs()
     }
 }
 
@@ -4,7 +4,8 @@ use crate::net::{
     socket::{Shutdown, Socket, SocketError},
     socket_addrs::ToSocketAddrs,
 };
-use core::{net::SocketAddrV4, time::Duration};
+use core::{fmt, net::SocketAddrV4, time::Duration};
+use wut_sys as sys;
 
 #[derive(Debug)]
 pub struct TcpListener {
@@ -13,14 +14,40 @@ pub struct TcpListener {
 }
 
 impl TcpListener {
+    /// Listen for incoming connections on the first address of `address` which can be bound.
+    ///
+    /// Binding to port `0` selects a free port, which can be retrieved via [TcpListener::local_addr].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wut::net::tcp::TcpListener;
+    ///
+    /// let listener = TcpListener::bind("0.0.0.0:23")?;
+    /// println!("telnet to me at {}:23", wut::net::local_ip()?);
+    ///
+    /// for stream in listener.incoming() {
+    ///     let mut stream = stream?;
+    ///     stream.write_all(b"hello from the Wii U\r\n")?;
+    /// }
+    /// ```
     pub fn bind(address: impl ToSocketAddrs) -> Result<Self, SocketError> {
         let socket = Socket::tcp()?;
         let address = socket.bind(address)?;
         socket.listen(16)?;
+        let address = socket.local_addr().unwrap_or(address);
 
         Ok(Self { socket, address })
     }
 
+    /// Address the listener is bound to.
+    pub fn local_addr(&self) -> Result<SocketAddrV4, SocketError> {
+        self.socket.local_addr()
+    }
+
+    /// Block until a client connects and return the connection together with the address of the client.
+    ///
+    /// In non-blocking mode, this fails with [SystemError::NoMoreProcesses][crate::net::errno::SystemError::NoMoreProcesses] (`EAGAIN`) if no client is waiting.
     pub fn accept(&self) -> Result<(TcpStream, SocketAddrV4), SocketError> {
         let (client, address) = self.socket.accept()?;
         Ok((
@@ -32,9 +59,14 @@ impl TcpListener {
         ))
     }
 
+    /// Iterator over incoming connections. Never returns `None`.
     pub fn incoming(&self) -> Incoming<'_> {
         Incoming { listener: &self }
     }
+
+    pub fn set_nonblocking(&self, nonblocking: bool) -> Result<(), SocketError> {
+        self.socket.set_nonblocking(nonblocking)
+    }
 }
 
 pub struct Incoming<'a> {
@@ -55,18 +87,46 @@ pub struct TcpStream {
 }
 
 impl TcpStream {
-    pub fn connect() -> Self {
-        todo!()
+    /// Open a TCP connection to the first address of `address` which accepts it.
+    pub fn connect(address: impl ToSocketAddrs) -> Result<Self, SocketError> {
+        let socket = Socket::tcp()?;
+        let address = socket.connect(address)?;
+
+        Ok(Self { socket, address })
     }
 
     pub fn read(&mut self, buf: &mut [u8]) -> Result<usize, SocketError> {
         self.socket.read(buf)
     }
 
+    /// Read exactly enough bytes to fill `buf`.
+    pub fn read_exact(&mut self, mut buf: &mut [u8]) -> Result<(), SocketError> {
+        while !buf.is_empty() {
+            let n = self.read(buf)?;
+            buf = &mut buf[n..];
+        }
+        Ok(())
+    }
+
     pub fn write(&mut self, buf: &[u8]) -> Result<usize, SocketError> {
         self.socket.write(buf)
     }
 
+    /// Write the entire `buf`, retrying partial writes.
+    pub fn write_all(&mut self, mut buf: &[u8]) -> Result<(), SocketError> {
+        while !buf.is_empty() {
+            let n = self.write(buf)?;
+            buf = &buf[n..];
+        }
+        Ok(())
+    }
+
+    /// Disable Nagle's algorithm, so small writes are sent immediately.
+    pub fn set_nodelay(&mut self, nodelay: bool) -> Result<(), SocketError> {
+        self.socket
+            .set_option(sys::IPPROTO_TCP, sys::TCP_NODELAY, &(nodelay as i32))
+    }
+
     pub fn shutdown(&mut self, how: Shutdown) -> Result<(), SocketError> {
         self.socket.shutdown(how)
     }
@@ -90,15 +150,23 @@ impl TcpStream {
         todo!()
     }
 
-    pub fn set_read_timeout(&mut self, _dur: Option<Duration>) -> Result<(), SocketError> {
-        todo!()
+    /// Set the timeout for [TcpStream::read]. `None` blocks indefinitely.
+    pub fn set_read_timeout(&mut self, dur: Option<Duration>) -> Result<(), SocketError> {
+        self.socket.set_timeout(sys::SO_RCVTIMEO, dur)
     }
 
-    pub fn set_write_timeout(&mut self, _dur: Option<Duration>) -> Result<(), SocketError> {
-        todo!()
+    /// Set the timeout for [TcpStream::write]. `None` blocks indefinitely.
+    pub fn set_write_timeout(&mut self, dur: Option<Duration>) -> Result<(), SocketError> {
+        self.socket.set_timeout(sys::SO_SNDTIMEO, dur)
     }
 
-    pub fn set_nonblocking(&mut self, _nonblocking: bool) -> Result<(), SocketError> {
-        todo!()
+    pub fn set_nonblocking(&mut self, nonblocking: bool) -> Result<(), SocketError> {
+        self.socket.set_nonblocking(nonblocking)
+    }
+}
+
+impl fmt::Write for TcpStream {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_all(s.as_bytes()).map_err(|_| fmt::Error)
     }
 }
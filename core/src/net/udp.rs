@@ -0,0 +1,93 @@
+// udp
+
+use crate::net::{
+    socket::{Socket, SocketError},
+    socket_addrs::ToSocketAddrs,
+};
+use core::{net::SocketAddrV4, time::Duration};
+use wut_sys as sys;
+
+/// A UDP socket.
+///
+/// # Example
+///
+/// Echo every datagram back to its sender.
+///
+/// ```
+/// use wut::net::UdpSocket;
+///
+/// let socket = UdpSocket::bind("0.0.0.0:4405").unwrap();
+/// let mut buf = [0; 1500];
+///
+/// while wut::process::running() {
+///     let (n, sender) = socket.recv_from(&mut buf).unwrap();
+///     socket.send_to(&buf[..n], sender).unwrap();
+/// }
+/// ```
+#[derive(Debug)]
+pub struct UdpSocket {
+    socket: Socket,
+    pub address: SocketAddrV4,
+}
+
+impl UdpSocket {
+    /// Create a UDP socket bound to the first available address of `address`.
+    ///
+    /// Port `0` lets the system choose a port.
+    pub fn bind(address: impl ToSocketAddrs) -> Result<Self, SocketError> {
+        let socket = Socket::udp()?;
+        let address = socket.bind(address)?;
+
+        Ok(Self { socket, address })
+    }
+
+    /// Send a datagram to the first address of `address`.
+    pub fn send_to(&self, buf: &[u8], address: impl ToSocketAddrs) -> Result<usize, SocketError> {
+        match address.to_socket_addrs()?.next() {
+            Some(address) => self.socket.send_to(buf, address),
+            None => Err(SocketError::NoAvailableAddress),
+        }
+    }
+
+    /// Receive a datagram and the address it was sent from.
+    ///
+    /// If `buf` is too small, the rest of the datagram is discarded.
+    pub fn recv_from(&self, buf: &mut [u8]) -> Result<(usize, SocketAddrV4), SocketError> {
+        self.socket.recv_from(buf)
+    }
+
+    /// Set the default destination for [UdpSocket::send] and only receive datagrams from this address.
+    pub fn connect(&self, address: impl ToSocketAddrs) -> Result<SocketAddrV4, SocketError> {
+        self.socket.connect(address)
+    }
+
+    /// Send a datagram to the connected address.
+    pub fn send(&self, buf: &[u8]) -> Result<usize, SocketError> {
+        self.socket.send(buf)
+    }
+
+    /// Receive a datagram from the connected address.
+    pub fn recv(&self, buf: &mut [u8]) -> Result<usize, SocketError> {
+        self.socket.recv(buf)
+    }
+
+    /// Allow sending to broadcast addresses like `255.255.255.255`.
+    pub fn set_broadcast(&self, broadcast: bool) -> Result<(), SocketError> {
+        self.socket
+            .set_option(sys::SOL_SOCKET, sys::SO_BROADCAST, &(broadcast as i32))
+    }
+
+    pub fn set_nonblocking(&self, nonblocking: bool) -> Result<(), SocketError> {
+        self.socket.set_nonblocking(nonblocking)
+    }
+
+    /// Set the timeout for receiving. `None` blocks indefinitely.
+    pub fn set_read_timeout(&self, dur: Option<Duration>) -> Result<(), SocketError> {
+        self.socket.set_timeout(sys::SO_RCVTIMEO, dur)
+    }
+
+    /// Set the timeout for sending. `None` blocks indefinitely.
+    pub fn set_write_timeout(&self, dur: Option<Duration>) -> Result<(), SocketError> {
+        self.socket.set_timeout(sys::SO_SNDTIMEO, dur)
+    }
+}
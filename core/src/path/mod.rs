@@ -1046,6 +1046,13 @@ impl PathBuf {
     pub fn absolute(&self) -> Result<PathBuf, fs::FilesystemError> {
         absolute(self)
     }
+
+    /// Returns the absolute form of the path with all intermediate components resolved and verified to exist.
+    ///
+    /// More info: [canonicalize]
+    pub fn canonicalize(&self) -> Result<PathBuf, fs::FilesystemError> {
+        canonicalize(self)
+    }
 }
 
 impl From<&Path> for Box<Path> {
@@ -1570,6 +1577,63 @@ impl Path {
             .ok_or(StripPrefixError(()))
     }
 
+    /// Returns a path that, when joined onto `base`, leads to `self`.
+    ///
+    /// Unlike [`strip_prefix`], `base` does not need to be a prefix of `self`: for every component of `base` after the common ancestor, a `..` is emitted. The filesystem is not accessed, so links are not taken into account. Returns an empty path if both paths are equal.
+    ///
+    /// Returns `None` if only one of the paths is absolute, or if `base` contains a `..` after the common ancestor.
+    ///
+    /// [`strip_prefix`]: #method.strip_prefix
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use path::{Path, PathBuf};
+    ///
+    /// let path = Path::new("/vol/content/textures/grass.png");
+    ///
+    /// assert_eq!(path.relative_to("/vol/content"), Some(PathBuf::from("textures/grass.png")));
+    /// assert_eq!(path.relative_to("/vol/content/sounds/ui"), Some(PathBuf::from("../../textures/grass.png")));
+    /// assert_eq!(path.relative_to("/vol/save"), Some(PathBuf::from("../content/textures/grass.png")));
+    /// assert_eq!(path.relative_to("content"), None);
+    /// ```
+    pub fn relative_to<P: AsRef<Path>>(&self, base: P) -> Option<PathBuf> {
+        let base = base.as_ref();
+        if self.is_absolute() != base.is_absolute() {
+            return None;
+        }
+
+        let mut path = self
+            .components()
+            .filter(|c| *c != Component::CurDir)
+            .peekable();
+        let mut base = base
+            .components()
+            .filter(|c| *c != Component::CurDir)
+            .peekable();
+
+        while let (Some(a), Some(b)) = (path.peek(), base.peek()) {
+            if a != b {
+                break;
+            }
+            path.next();
+            base.next();
+        }
+
+        let mut relative = PathBuf::new();
+        for component in base {
+            match component {
+                Component::Normal(_) => relative.push(".."),
+                _ => return None,
+            }
+        }
+        for component in path {
+            relative.push(component);
+        }
+
+        Some(relative)
+    }
+
     /// Determines whether `base` is a prefix of `self`.
     ///
     /// Only considers whole path components to match.
@@ -1794,6 +1858,60 @@ impl Path {
         }
     }
 
+    /// Produces an iterator over the [`Component`]s of the path together with
+    /// the byte range they occupy in [`as_str`].
+    ///
+    /// The components are the same as those of [`components`]. For
+    /// [`RootDir`] and [`CurDir`], the range covers the single `/` or `.`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use path::{Component, Path};
+    ///
+    /// let path = Path::new("/vol//content/./data.bin");
+    /// let mut indices = path.component_indices();
+    ///
+    /// assert_eq!(indices.next(), Some((0..1, Component::RootDir)));
+    /// assert_eq!(indices.next(), Some((1..4, Component::Normal("vol"))));
+    /// assert_eq!(indices.next(), Some((6..13, Component::Normal("content"))));
+    /// assert_eq!(indices.next(), Some((16..24, Component::Normal("data.bin"))));
+    /// assert_eq!(indices.next(), None);
+    ///
+    /// // everything up to and including `content`
+    /// assert_eq!(&path.as_str()[..13], "/vol//content");
+    /// ```
+    ///
+    /// [`Component`]: enum.Component.html
+    /// [`RootDir`]: enum.Component.html#variant.RootDir
+    /// [`CurDir`]: enum.Component.html#variant.CurDir
+    /// [`as_str`]: struct.Path.html#method.as_str
+    /// [`components`]: struct.Path.html#method.components
+    pub fn component_indices(&self) -> impl Iterator<Item = (ops::Range<usize>, Component<'_>)> {
+        let base = self.as_u8_slice().as_ptr() as usize;
+        let mut components = self.components();
+
+        iter::from_fn(move || {
+            let component = components.next()?;
+
+            // the component and at most one trailing separator have been consumed
+            let consumed = components.path.as_ptr() as usize - base;
+            let range = match component {
+                Component::RootDir | Component::CurDir => consumed - 1..consumed,
+                _ => {
+                    let end = if consumed > 0 && self.as_u8_slice()[consumed - 1] == b'/' {
+                        consumed - 1
+                    } else {
+                        consumed
+                    };
+                    end - component.as_str().len()..end
+                }
+            };
+
+            Some((range, component))
+        })
+    }
+
     /// Produces an iterator over the path's components viewed as `str`
     /// slices.
     ///
@@ -1840,6 +1958,13 @@ impl Path {
         absolute(self)
     }
 
+    /// Returns the absolute form of the path with all intermediate components resolved and verified to exist.
+    ///
+    /// More info: [canonicalize]
+    pub fn canonicalize(&self) -> Result<PathBuf, fs::FilesystemError> {
+        canonicalize(self)
+    }
+
     // FS helper functions
 
     pub fn is_dir(&self) -> bool {
@@ -1911,6 +2036,56 @@ pub fn absolute<P: AsRef<Path>>(path: P) -> Result<PathBuf, fs::FilesystemError>
     Ok(result)
 }
 
+/// Returns the absolute form of the path with all intermediate components resolved and verified to exist.
+///
+/// Unlike [absolute], this accesses the filesystem: each directory is checked before a `..` component is applied, and the final path must exist. A missing component results in [NotFound][fs::FilesystemError::NotFound].
+///
+/// CafeOS does not expose the target of links (see [FileType::is_symlink][fs::FileType::is_symlink]), so links are kept as they are.
+///
+/// # Example
+///
+/// ```
+/// use wut::path::Path;
+///
+/// let path = Path::new("/vol/external01/wiiu/../wiiu/apps").canonicalize()?;
+/// assert_eq!(path, Path::new("/vol/external01/wiiu/apps"));
+/// ```
+pub fn canonicalize<P: AsRef<Path>>(path: P) -> Result<PathBuf, fs::FilesystemError> {
+    let path = path.as_ref();
+    if path.as_str().is_empty() {
+        return Err(fs::FilesystemError::NotFound);
+    }
+
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        env::current_dir()?.join(path)
+    };
+    let mut result = PathBuf::new();
+
+    for part in path.components() {
+        match part {
+            Component::RootDir => {
+                result.push(MAIN_SEPARATOR.to_string());
+            }
+            Component::CurDir => (),
+            Component::ParentDir => {
+                if result.parent().is_some() {
+                    fs::metadata(&result)?;
+                }
+                result.pop();
+            }
+            Component::Normal(name) => {
+                result.push(name);
+            }
+        }
+    }
+
+    fs::metadata(&result)?;
+
+    Ok(result)
+}
+
 impl AsRef<str> for Path {
     fn as_ref(&self) -> &str {
         &self.inner
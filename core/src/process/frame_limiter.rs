@@ -0,0 +1,68 @@
+use crate::time::{self, Instant};
+use core::time::Duration;
+
+/// Caps the frame rate of a render loop.
+///
+/// # Example
+///
+/// ```
+/// use wut::process::{self, FrameLimiter};
+///
+/// let mut limiter = FrameLimiter::new(60);
+/// let mut x = 0.0;
+///
+/// while process::running() {
+///     let dt = limiter.wait();
+///     x += 100.0 * dt.as_secs_f32(); // 100 pixels per second
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FrameLimiter {
+    budget: Duration,
+    next: Instant,
+    last: Instant,
+}
+
+impl FrameLimiter {
+    /// Create a limiter targeting `fps` frames per second. An `fps` of `0` disables limiting.
+    pub fn new(fps: u32) -> Self {
+        let budget = match fps {
+            0 => Duration::ZERO,
+            fps => Duration::from_secs(1) / fps,
+        };
+        let now = Instant::now();
+
+        Self {
+            budget,
+            next: now + budget,
+            last: now,
+        }
+    }
+
+    /// Sleep for the remainder of the current frame.
+    ///
+    /// Returns the time since the previous call (or since [FrameLimiter::new]), which can be used as the delta time of the frame. If the frame took longer than the budget, no sleep happens and the schedule restarts from now instead of trying to catch up.
+    pub fn wait(&mut self) -> Duration {
+        let remaining = self.next.duration_since(Instant::now());
+        if !remaining.is_zero() {
+            time::sleep(remaining);
+        }
+
+        let now = Instant::now();
+        let delta = now.duration_since(self.last);
+        self.last = now;
+
+        self.next += self.budget;
+        if self.next.duration_since(now).is_zero() {
+            self.next = now + self.budget;
+        }
+
+        delta
+    }
+
+    /// Target duration of one frame.
+    #[inline]
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+}
@@ -1,16 +1,42 @@
 //! A module for working with processes.
 //!
-//! This module provides functions to initialize and deinitialize the application process, check if the process should continue running or exit, and reboot the system.
+//! This module provides functions to initialize and deinitialize the application process, check if the process should continue running or exit, and return to the menu, reboot or shut down the system.
 
-use crate::logger; // fs, screen
+use crate::{
+    env, logger,
+    sync::{LazyLock, Mutex},
+}; // fs, screen
+use alloc::{boxed::Box, vec::Vec};
+use core::ffi;
 use flagset::FlagSet;
 use wut_sys as sys;
 
+mod frame_limiter;
+
+pub use frame_limiter::FrameLimiter;
+
+static AT_EXIT: LazyLock<Mutex<Vec<Box<dyn FnOnce() + Send>>>> =
+    LazyLock::new(|| Mutex::new(Vec::new()));
+
 /// Initialize application process.
 ///
 /// This function is required to be ran as soon as possible in `main`. If using the `#[wut::main]` macro, you mustn't call it manually.
 pub fn init(stdout: impl Into<FlagSet<logger::Channel>>) {
+    unsafe { init_with_args(stdout, 0, core::ptr::null()) }
+}
+
+/// Like [init], but also stores the arguments passed to `main`, making them available via [env::args].
+///
+/// # Safety
+///
+/// `argv` must point to `argc` valid, null terminated C strings or be null.
+pub unsafe fn init_with_args(
+    stdout: impl Into<FlagSet<logger::Channel>>,
+    argc: ffi::c_int,
+    argv: *const *const ffi::c_char,
+) {
     unsafe {
+        env::init_args(argc, argv);
         sys::WHBProcInit();
         let _ = logger::init(stdout.into()).unwrap();
         sys::OSReportInfo(c"process::init".as_ptr());
@@ -24,6 +50,8 @@ pub fn deinit() {
     unsafe {
         sys::OSReportInfo(c"process::deinit".as_ptr());
     }
+    run_at_exit();
+
     // screen::OSSCREEN.clear();
     // fs::FS.clear();
 
@@ -34,6 +62,36 @@ pub fn deinit() {
     }
 }
 
+/// Register a function to be called by [deinit].
+///
+/// Registered functions are called in reverse order of registration, i.e. the last registered function runs first. This allows libraries to release their resources deterministically when `main` returns. Functions registered while the hooks are running are called as well.
+///
+/// # Example
+///
+/// ```
+/// wut::process::at_exit(|| println!("goodbye"));
+/// ```
+pub fn at_exit<F: FnOnce() + Send + 'static>(f: F) {
+    if let Ok(mut hooks) = AT_EXIT.lock() {
+        hooks.push(Box::new(f));
+    }
+}
+
+/// Run all [at_exit] hooks in LIFO order. The lock is not held while a hook runs, so hooks may register further hooks.
+fn run_at_exit() {
+    loop {
+        let hook = match AT_EXIT.lock() {
+            Ok(mut hooks) => hooks.pop(),
+            Err(_) => None,
+        };
+
+        match hook {
+            Some(f) => f(),
+            None => break,
+        }
+    }
+}
+
 /// Check if the OS wants to move application out of foreground.
 ///
 /// Should be ran in reasonable intervals or OS may be unresponseable.
@@ -67,3 +125,43 @@ pub fn reboot() -> ! {
     }
     exit()
 }
+
+/// Run the [at_exit] hooks and return to the Wii U Menu.
+///
+/// Unlike [exit], registered hooks get the chance to release their resources and save data first. Destructors on the stack are still not run.
+///
+/// # Example
+///
+/// ```
+/// if gamepad.poll()?.trigger.contains(Button::Home) {
+///     wut::process::return_to_menu();
+/// }
+/// ```
+pub fn return_to_menu() -> ! {
+    run_at_exit();
+    exit()
+}
+
+/// Run the [at_exit] hooks and fully relaunch the system before returning to the Wii U Menu.
+///
+/// This is the clean counterpart of [reboot], e.g. to apply system settings which only take effect after a restart.
+pub fn relaunch() -> ! {
+    run_at_exit();
+    reboot()
+}
+
+/// Run the [at_exit] hooks and turn off the console.
+pub fn shutdown() -> ! {
+    run_at_exit();
+    unsafe {
+        sys::OSReportInfo(c"process::shutdown".as_ptr());
+        sys::OSShutdown();
+    }
+    // the system waits for the application to release the foreground before shutting down
+    while running() {}
+    loop {
+        unsafe {
+            sys::_Exit(-1);
+        }
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! This module provides checks for validation of pointers. If non-statically known addresses are accessed via pointers it is highly recommended to check for validity to avoid system crashes.
 //!
+//! It also contains [DmaBuffer], for memory which is shared with hardware like the GPU or the filesystem.
+//!
 //! # Example
 //!
 //! ```rust
@@ -13,6 +15,10 @@
 //! }
 //! ```
 
+use core::{
+    alloc::Layout,
+    ops::{Deref, DerefMut},
+};
 use wut_sys as sys;
 
 pub use core::ptr::*;
@@ -24,3 +30,143 @@ pub use core::ptr::*;
 pub fn is_valid<T>(ptr: *const T) -> bool {
     unsafe { sys::OSIsAddressValid(ptr as u32) == 1 }
 }
+
+/// Size of a CPU data cache line. Cache operations always affect whole lines.
+pub const CACHE_LINE: usize = 0x40;
+
+/// A heap allocated byte buffer suitable for DMA.
+///
+/// The CPU caches are not coherent with other hardware. Data written by the CPU must be [flushed][DmaBuffer::flush] before the hardware reads it, and the cache must be [invalidated][DmaBuffer::invalidate] after the hardware has written to it and before the CPU reads it.
+///
+/// The buffer is aligned to at least [CACHE_LINE] and its allocation is padded to a whole number of cache lines, so cache operations never affect neighbouring memory. The content is zeroed on creation.
+///
+/// # Example
+///
+/// ```
+/// use wut::ptr::DmaBuffer;
+///
+/// let mut buffer = DmaBuffer::new(1024);
+/// buffer.as_mut_slice().fill(0xff);
+/// // make the data visible to the hardware
+/// buffer.flush();
+///
+/// // ... hardware writes into the buffer ...
+///
+/// // discard stale cache lines before reading
+/// buffer.invalidate();
+/// let data = buffer.as_slice();
+/// ```
+pub struct DmaBuffer {
+    ptr: NonNull<u8>,
+    len: usize,
+    /// Allocated size, rounded up to whole cache lines.
+    size: usize,
+}
+
+impl DmaBuffer {
+    /// Allocate `len` zeroed bytes aligned to [CACHE_LINE].
+    #[inline]
+    pub fn new(len: usize) -> Self {
+        Self::with_alignment(len, CACHE_LINE)
+    }
+
+    /// Allocate `len` zeroed bytes aligned to `align`, e.g. `0x100` for GPU resources.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two. Aborts if the allocation fails.
+    pub fn with_alignment(len: usize, align: usize) -> Self {
+        assert!(align.is_power_of_two(), "alignment must be a power of two");
+        let align = align.max(CACHE_LINE);
+        let size = len.max(1).next_multiple_of(CACHE_LINE);
+
+        let ptr = unsafe { sys::MEMAllocFromDefaultHeapEx.unwrap()(size as u32, align as i32) };
+        let Some(ptr) = NonNull::new(ptr as *mut u8) else {
+            alloc::alloc::handle_alloc_error(Layout::from_size_align(size, align).unwrap());
+        };
+
+        unsafe {
+            ptr.as_ptr().write_bytes(0, size);
+        }
+
+        Self { ptr, len, size }
+    }
+
+    /// Allocate a buffer containing a copy of `data`.
+    pub fn from_slice(data: &[u8]) -> Self {
+        let mut buffer = Self::new(data.len());
+        buffer.as_mut_slice().copy_from_slice(data);
+        buffer
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn as_ptr(&self) -> *const u8 {
+        self.ptr.as_ptr()
+    }
+
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.ptr.as_ptr()
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { core::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Write the cached content back to memory, so it can be read by the hardware.
+    pub fn flush(&self) {
+        unsafe {
+            sys::DCFlushRange(self.ptr.as_ptr() as *mut _, self.size as u32);
+        }
+    }
+
+    /// Discard the cached content, so data written by the hardware can be read.
+    ///
+    /// Unflushed writes by the CPU are lost.
+    pub fn invalidate(&mut self) {
+        unsafe {
+            sys::DCInvalidateRange(self.ptr.as_ptr() as *mut _, self.size as u32);
+        }
+    }
+}
+
+impl Deref for DmaBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl DerefMut for DmaBuffer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl Drop for DmaBuffer {
+    fn drop(&mut self) {
+        unsafe {
+            sys::MEMFreeToDefaultHeap.unwrap()(self.ptr.as_ptr() as *mut _);
+        }
+    }
+}
+
+unsafe impl Send for DmaBuffer {}
+unsafe impl Sync for DmaBuffer {}
@@ -0,0 +1,146 @@
+use crate::screen::{Color, DisplayType, Screen, TextAlign};
+use alloc::{collections::VecDeque, string::String};
+use core::fmt;
+
+/// Tab stops are placed every `TAB_WIDTH` columns.
+const TAB_WIDTH: u32 = 4;
+
+/// A scrolling text console on top of a [Screen].
+///
+/// Text is appended at the cursor. Lines are wrapped at the right edge of the screen and the content scrolls up once the bottom is reached. Every write redraws the whole screen and updates it, so the console should not be mixed with other drawing on the same display.
+///
+/// Implements [fmt::Write], so it can be used with `write!` or the [cprint!][crate::cprint] and [cprintln!][crate::cprintln] macros. Writing with `write!` may update the screen multiple times per call, while the macros update it once.
+///
+/// # Example
+///
+/// ```
+/// use wut::screen::{self, Console};
+/// use wut::{cprint, cprintln};
+///
+/// let mut console = Console::new(screen::drc());
+///
+/// cprintln!(console, "Copying files...");
+/// for i in 0..10 {
+///     cprint!(console, "{i} ");
+/// }
+/// cprintln!(console);
+/// cprintln!(console, "Done!");
+/// ```
+pub struct Console<Display: DisplayType> {
+    screen: Screen<Display>,
+    /// Visible lines, the last one contains the cursor.
+    lines: VecDeque<String>,
+    background: Color,
+}
+
+impl<Display: DisplayType> Console<Display> {
+    pub fn new(screen: Screen<Display>) -> Self {
+        let mut lines = VecDeque::with_capacity(Display::rows() as usize);
+        lines.push_back(String::new());
+
+        Self {
+            screen,
+            lines,
+            background: Color::black(),
+        }
+    }
+
+    /// Set the color behind the text. Defaults to black.
+    pub fn background(mut self, color: Color) -> Self {
+        self.background = color;
+        self
+    }
+
+    /// Cursor position as column and row.
+    pub fn cursor(&self) -> (u32, u32) {
+        let row = self.lines.len() - 1;
+        (self.lines[row].chars().count() as u32, row as u32)
+    }
+
+    /// Remove all text and move the cursor to the top left corner.
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.lines.push_back(String::new());
+        self.redraw();
+    }
+
+    pub fn screen(&self) -> &Screen<Display> {
+        &self.screen
+    }
+
+    pub fn into_screen(self) -> Screen<Display> {
+        self.screen
+    }
+
+    fn push(&mut self, c: char) {
+        match c {
+            '\n' => self.new_line(),
+            '\r' => self.lines.back_mut().unwrap().clear(),
+            '\t' => {
+                let (col, _) = self.cursor();
+                for _ in 0..TAB_WIDTH - col % TAB_WIDTH {
+                    self.push(' ');
+                }
+            }
+            c if c.is_control() => (),
+            c => {
+                if self.cursor().0 >= Display::columns() {
+                    self.new_line();
+                }
+                self.lines.back_mut().unwrap().push(c);
+            }
+        }
+    }
+
+    fn new_line(&mut self) {
+        if self.lines.len() >= Display::rows() as usize {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(String::new());
+    }
+
+    fn redraw(&self) {
+        self.screen.fill(self.background);
+        for (row, line) in self.lines.iter().enumerate() {
+            self.screen.text(line, 0u32, row as u32, TextAlign::Left);
+        }
+        self.screen.update();
+    }
+}
+
+impl<Display: DisplayType> fmt::Write for Console<Display> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        s.chars().for_each(|c| self.push(c));
+        self.redraw();
+        Ok(())
+    }
+}
+
+/// Prints to a [Console][crate::screen::Console].
+///
+/// Formats the whole message before writing it, so the screen is only updated once.
+#[macro_export]
+macro_rules! cprint {
+    ($console:expr, $($arg:tt)*) => {{
+        extern crate alloc;
+        use alloc::fmt::format;
+
+        let _ = ::core::fmt::Write::write_str(&mut $console, &format(format_args!($($arg)*)));
+    }};
+}
+
+/// Prints to a [Console][crate::screen::Console], with a newline.
+#[macro_export]
+macro_rules! cprintln {
+    ($console:expr) => {
+        $crate::cprint!($console, "\n")
+    };
+    ($console:expr, $($arg:tt)*) => {{
+        extern crate alloc;
+        use alloc::fmt::format;
+
+        let mut text = format(format_args!($($arg)*));
+        text.push('\n');
+        let _ = ::core::fmt::Write::write_str(&mut $console, &text);
+    }};
+}
@@ -6,33 +6,43 @@
 //!
 //! Each screen has only one framebuffer. This means any [Screen] instances will share and write to the same framebuffer for their respective display.
 
+mod console;
 mod position;
 
-pub use crate::gx2::color::Color;
-use crate::rrc::{Rrc, RrcGuard};
-use alloc::{ffi::CString, string::String};
+pub use crate::gx2::color::{Color, ColorParseError};
+pub use console::Console;
+use crate::{
+    foreground::MemoryReclaim,
+    fs::{self, FilesystemError},
+    path::Path,
+    rrc::{Rrc, RrcGuard},
+    sync::{ConstMutex, LazyLock, Mutex},
+    time::{self, Duration, Instant},
+    utils::text_wrap,
+};
+use alloc::{ffi::CString, string::String, vec, vec::Vec};
 use core::{ffi, marker::PhantomData, ptr};
 use position::Position;
-pub use position::{TextAlign, TextPosition};
+pub use position::{Offset, TextAlign, TextPosition};
 use wut_sys as sys;
 
+/// Time between two refreshes of the display at 60 Hz.
+const FRAME_TIME: Duration = Duration::from_nanos(16_666_667);
+
+/// Time of the last [Screen::update_paced].
+static LAST_PACED_UPDATE: ConstMutex<Option<Instant>> = LazyLock::new(|| Mutex::new(None));
+
 pub(crate) static OSSCREEN: Rrc = Rrc::new(
-    || unsafe {
-        use sys::ProcUICallbackType as T;
-
-        sys::OSScreenInit();
-        let _ = _alloc_framebuffer(ptr::null_mut());
-        sys::ProcUIRegisterCallback(
-            T::PROCUI_CALLBACK_ACQUIRE,
-            Some(_alloc_framebuffer),
-            ptr::null_mut(),
-            100,
-        );
-        sys::ProcUIRegisterCallback(
-            T::PROCUI_CALLBACK_RELEASE,
-            Some(_dealloc_framebuffer),
-            ptr::null_mut(),
-            100,
+    || {
+        unsafe {
+            sys::OSScreenInit();
+        }
+        alloc_framebuffer();
+        MemoryReclaim::register(
+            "screen::framebuffer",
+            MemoryReclaim::SCREEN,
+            dealloc_framebuffer,
+            alloc_framebuffer,
         );
     },
     || /*unsafe*/ {
@@ -44,6 +54,8 @@ pub(crate) static OSSCREEN: Rrc = Rrc::new(
 struct Framebuffer {
     ptr: *mut ffi::c_void,
     size: u32,
+    /// Number of buffer flips. Used to determine which of the two buffers is visible.
+    flips: u32,
 }
 
 impl Framebuffer {
@@ -51,6 +63,7 @@ impl Framebuffer {
         Framebuffer {
             ptr: ptr::null_mut(),
             size: 0,
+            flips: 0,
         }
     }
 }
@@ -60,6 +73,7 @@ impl From<(*mut ffi::c_void, u32)> for Framebuffer {
         Framebuffer {
             ptr: value.0,
             size: value.1,
+            flips: 0,
         }
     }
 }
@@ -68,7 +82,8 @@ const FRAMEBUFFER_HEAP_TAG: u32 = 0x8E8B30C2;
 static mut FRAMEBUFFER_TV: Framebuffer = Framebuffer::new();
 static mut FRAMEBUFFER_DRC: Framebuffer = Framebuffer::new();
 
-unsafe extern "C" fn _alloc_framebuffer(_: *mut ffi::c_void) -> u32 {
+/// Allocate the framebuffers from MEM1, which is only available in the foreground.
+fn alloc_framebuffer() {
     unsafe {
         let heap = sys::MEMGetBaseHeapHandle(sys::MEMBaseHeapType::MEM_BASE_HEAP_MEM1);
         let _ = sys::MEMRecordStateForFrmHeap(heap, FRAMEBUFFER_HEAP_TAG);
@@ -88,11 +103,9 @@ unsafe extern "C" fn _alloc_framebuffer(_: *mut ffi::c_void) -> u32 {
         sys::OSScreenSetBufferEx(sys::OSScreenID::SCREEN_TV, FRAMEBUFFER_TV.ptr);
         sys::OSScreenSetBufferEx(sys::OSScreenID::SCREEN_DRC, FRAMEBUFFER_DRC.ptr);
     }
-
-    0
 }
 
-unsafe extern "C" fn _dealloc_framebuffer(_: *mut ffi::c_void) -> u32 {
+fn dealloc_framebuffer() {
     unsafe {
         let heap = sys::MEMGetBaseHeapHandle(sys::MEMBaseHeapType::MEM_BASE_HEAP_MEM1);
         let _ = sys::MEMFreeByStateToFrmHeap(heap, FRAMEBUFFER_HEAP_TAG);
@@ -100,8 +113,6 @@ unsafe extern "C" fn _dealloc_framebuffer(_: *mut ffi::c_void) -> u32 {
         FRAMEBUFFER_TV = Framebuffer::new();
         FRAMEBUFFER_DRC = Framebuffer::new();
     }
-
-    0
 }
 
 pub struct TV;
@@ -136,38 +147,22 @@ impl DisplayType for TV {
         TV::resolution().1
     }
 
+    /// Resolution of the connected TV or, if no TV is connected, the configured one. Falls back to 1280x720 if neither is available.
     fn resolution() -> (u32, u32) {
-        use sys::AVMTvResolution as R;
-        // it is (theoretically?) possible that no resolution is found?!
-        let mut resolution = R::Type::default();
-
-        unsafe {
-            sys::AVMGetTVScanMode(&mut resolution);
-
-            if resolution == 0 {
-                if sys::AVMReadSystemVideoResConfig(&mut resolution) != 0 {
-                    panic!("No resolution was returned by the system");
+        TV::try_resolution()
+            .or_else(|| {
+                let mut resolution = sys::AVMTvResolution::Type::default();
+                let status = unsafe { sys::AVMReadSystemVideoResConfig(&mut resolution) };
+                if status == 0 {
+                    tv_resolution(resolution)
+                } else {
+                    None
                 }
-            }
-        }
-        match resolution {
-            R::AVM_TV_RESOLUTION_576I | R::AVM_TV_RESOLUTION_576P => (720, 576),
-            R::AVM_TV_RESOLUTION_480I
-            | R::AVM_TV_RESOLUTION_480I_PAL60
-            | R::AVM_TV_RESOLUTION_480P => (720, 480),
-            R::AVM_TV_RESOLUTION_720P
-            | R::AVM_TV_RESOLUTION_720P_3D
-            | R::AVM_TV_RESOLUTION_720P_50HZ => (1280, 720),
-            R::AVM_TV_RESOLUTION_1080I
-            | R::AVM_TV_RESOLUTION_1080I_50HZ
-            | R::AVM_TV_RESOLUTION_1080P
-            | R::AVM_TV_RESOLUTION_1080P_50HZ => (1920, 1080),
-            0 => {
+            })
+            .unwrap_or_else(|| {
                 crate::println!("Fallback to default resolution (1280, 720)");
                 (1280, 720)
-            }
-            _ => panic!("Returned resolution couldn't be matched"),
-        }
+            })
     }
 
     fn rows() -> u32 {
@@ -182,10 +177,42 @@ impl DisplayType for TV {
         unsafe {
             sys::DCFlushRange(FRAMEBUFFER_TV.ptr, FRAMEBUFFER_TV.size);
             sys::OSScreenFlipBuffersEx(Self::id());
+            FRAMEBUFFER_TV.flips = FRAMEBUFFER_TV.flips.wrapping_add(1);
         }
     }
 }
 
+impl TV {
+    /// Resolution of the connected TV.
+    ///
+    /// Returns `None` if the system reports no scan mode, i.e. no TV is connected.
+    pub fn try_resolution() -> Option<(u32, u32)> {
+        let mut resolution = sys::AVMTvResolution::Type::default();
+        unsafe {
+            sys::AVMGetTVScanMode(&mut resolution);
+        }
+        tv_resolution(resolution)
+    }
+}
+
+fn tv_resolution(resolution: sys::AVMTvResolution::Type) -> Option<(u32, u32)> {
+    use sys::AVMTvResolution as R;
+    match resolution {
+        R::AVM_TV_RESOLUTION_576I | R::AVM_TV_RESOLUTION_576P => Some((720, 576)),
+        R::AVM_TV_RESOLUTION_480I | R::AVM_TV_RESOLUTION_480I_PAL60 | R::AVM_TV_RESOLUTION_480P => {
+            Some((720, 480))
+        }
+        R::AVM_TV_RESOLUTION_720P
+        | R::AVM_TV_RESOLUTION_720P_3D
+        | R::AVM_TV_RESOLUTION_720P_50HZ => Some((1280, 720)),
+        R::AVM_TV_RESOLUTION_1080I
+        | R::AVM_TV_RESOLUTION_1080I_50HZ
+        | R::AVM_TV_RESOLUTION_1080P
+        | R::AVM_TV_RESOLUTION_1080P_50HZ => Some((1920, 1080)),
+        _ => None,
+    }
+}
+
 impl DisplayType for DRC {
     fn id() -> sys::OSScreenID::Type {
         sys::OSScreenID::SCREEN_DRC
@@ -215,6 +242,7 @@ impl DisplayType for DRC {
         unsafe {
             sys::DCFlushRange(FRAMEBUFFER_DRC.ptr, FRAMEBUFFER_DRC.size);
             sys::OSScreenFlipBuffersEx(Self::id());
+            FRAMEBUFFER_DRC.flips = FRAMEBUFFER_DRC.flips.wrapping_add(1);
         }
     }
 }
@@ -267,6 +295,25 @@ impl<Display: DisplayType> Screen<Display> {
         Display::update();
     }
 
+    /// Like [update][Screen::update], but paces the buffer flips to the 60 Hz refresh rate of the display.
+    ///
+    /// If the previous call was less than one frame (~16.7 ms) ago, this sleeps for the rest of the frame before flipping the buffers. This caps redraws to the refresh rate, at the cost of up to one frame of additional latency. When updating both screens, only the first update should wait.
+    ///
+    /// # Note
+    ///
+    /// The flips are timed with [Instant], not synchronized to the vertical retrace of the display, so this does not prevent tearing. Waiting for the retrace requires GX2, which is not initialized by this module.
+    pub fn update_paced(&self) {
+        if let Ok(mut last) = LAST_PACED_UPDATE.lock() {
+            if let Some(elapsed) = last.map(|last| last.elapsed()) {
+                if elapsed < FRAME_TIME {
+                    time::sleep(FRAME_TIME - elapsed);
+                }
+            }
+            *last = Some(Instant::now());
+        }
+        Display::update();
+    }
+
     pub fn text<C: Position, R: Position>(&self, text: &str, col: C, row: R, align: TextAlign) {
         // let text = CString::new(text).unwrap();
         let text = String::from(text);
@@ -290,6 +337,157 @@ impl<Display: DisplayType> Screen<Display> {
         });
     }
 
+    /// Like [text][Screen::text], but at a [TextPosition] which is resolved against the size of this display.
+    pub fn text_at<P: Into<TextPosition>>(&self, text: &str, position: P) {
+        let position = position.into();
+        self.text(text, position.column, position.row, position.align);
+    }
+
+    /// Read the currently visible framebuffer.
+    ///
+    /// Returns `width() * height()` pixels as RGBA bytes in row-major order.
+    pub fn capture(&self) -> Vec<u8> {
+        let (width, height) = (Display::width(), Display::height());
+        let mut pixels = vec![0u8; (width * height * 4) as usize];
+
+        let (buffer, size, flips) = unsafe {
+            if Display::id() == sys::OSScreenID::SCREEN_TV {
+                (FRAMEBUFFER_TV.ptr, FRAMEBUFFER_TV.size, FRAMEBUFFER_TV.flips)
+            } else {
+                (FRAMEBUFFER_DRC.ptr, FRAMEBUFFER_DRC.size, FRAMEBUFFER_DRC.flips)
+            }
+        };
+        if buffer.is_null() {
+            return pixels;
+        }
+
+        // the framebuffer consists of two equally sized buffers which are flipped on update
+        let buffer_size = size / 2;
+        let pitch = (buffer_size / 4 / height).max(1);
+        let visible = (flips.wrapping_add(1) % 2) * buffer_size;
+
+        unsafe {
+            sys::DCFlushRange(buffer, size);
+        }
+
+        let buffer = unsafe { (buffer as *const u8).add(visible as usize) as *const u32 };
+        for y in 0..height {
+            for x in 0..width.min(pitch) {
+                let pixel = unsafe { buffer.add((y * pitch + x) as usize).read_volatile() };
+                let i = ((y * width + x) * 4) as usize;
+                pixels[i..i + 4].copy_from_slice(&pixel.to_be_bytes());
+            }
+        }
+
+        pixels
+    }
+
+    /// Fill the `w` x `h` pixel area with its top left corner at `(x, y)` with `color`.
+    ///
+    /// Parts of the area outside of the display are skipped.
+    pub fn clear_region<X: Position, Y: Position>(&self, x: X, y: Y, w: X, h: Y, color: Color) {
+        let Some((work, _, pitch)) = Self::buffers() else {
+            return;
+        };
+        let (width, height) = (Display::width(), Display::height());
+        let color: u32 = color.into();
+
+        let (x, y) = (x.into(width), y.into(height));
+        let x_end = x.saturating_add(w.into(width)).min(width);
+        let y_end = y.saturating_add(h.into(height)).min(height);
+
+        for row in y..y_end {
+            for col in x..x_end {
+                unsafe {
+                    work.add((row * pitch + col) as usize).write_volatile(color);
+                }
+            }
+        }
+    }
+
+    /// Shift the visible content up by `rows` text rows and fill the exposed rows at the bottom with `fill`.
+    ///
+    /// The result is based on what is currently visible and written to the buffer shown by the next [Screen::update]. Anything drawn since the last update is discarded. This allows console-style output:
+    ///
+    /// ```
+    /// use wut::screen::{self, Color, TextAlign};
+    ///
+    /// let screen = screen::drc();
+    /// let last = screen.rows() - 1;
+    ///
+    /// for i in 0.. {
+    ///     screen.scroll_up(1, Color::black());
+    ///     screen.text(&format!("line {i}"), 0, last, TextAlign::Left);
+    ///     screen.update();
+    /// }
+    /// ```
+    pub fn scroll_up(&self, rows: u32, fill: Color) {
+        let Some((work, visible, pitch)) = Self::buffers() else {
+            return;
+        };
+        let height = Display::height();
+        let shift = (rows * (height / Display::rows())).min(height);
+        let kept = height - shift;
+
+        unsafe {
+            ptr::copy(
+                visible.add((shift * pitch) as usize),
+                work,
+                (kept * pitch) as usize,
+            );
+        }
+        self.clear_region(0, kept, Display::width(), shift, fill);
+    }
+
+    /// Framebuffer being drawn to, currently visible framebuffer and pitch in pixels.
+    fn buffers() -> Option<(*mut u32, *mut u32, u32)> {
+        let (buffer, size, flips) = unsafe {
+            if Display::id() == sys::OSScreenID::SCREEN_TV {
+                (FRAMEBUFFER_TV.ptr, FRAMEBUFFER_TV.size, FRAMEBUFFER_TV.flips)
+            } else {
+                (FRAMEBUFFER_DRC.ptr, FRAMEBUFFER_DRC.size, FRAMEBUFFER_DRC.flips)
+            }
+        };
+        if buffer.is_null() {
+            return None;
+        }
+
+        unsafe {
+            sys::DCFlushRange(buffer, size);
+        }
+
+        // see [Screen::capture]
+        let buffer_size = size / 2;
+        let pitch = (buffer_size / 4 / Display::height()).max(1);
+        let visible = (flips.wrapping_add(1) % 2) * buffer_size;
+        let work = (flips % 2) * buffer_size;
+
+        let buffer = buffer as *mut u8;
+        unsafe {
+            Some((
+                buffer.add(work as usize) as *mut u32,
+                buffer.add(visible as usize) as *mut u32,
+                pitch,
+            ))
+        }
+    }
+
+    /// Like [Screen::text] but wraps lines on whitespace to fit into `max_width` columns.
+    ///
+    /// Words longer than `max_width` are split. Returns the number of rows written.
+    pub fn text_wrapped<C: Position, R: Position>(
+        &self,
+        text: &str,
+        col: C,
+        row: R,
+        align: TextAlign,
+        max_width: u32,
+    ) -> u32 {
+        let text = text_wrap(text, max_width as usize);
+        self.text(&text, col, row, align);
+        text.split('\n').count() as u32
+    }
+
     pub fn pixel<X: Position, Y: Position>(&self, x: X, y: Y, color: Color) {
         unsafe {
             sys::OSScreenPutPixelEx(
@@ -300,9 +498,150 @@ impl<Display: DisplayType> Screen<Display> {
             );
         }
     }
+
+    /// Draw a `width` x `height` bitmap with its top left corner at `(x, y)`.
+    ///
+    /// `pixels` are in row-major order. Parts of the bitmap outside of the display are skipped.
+    pub fn blit(&self, x: u32, y: u32, width: u32, height: u32, pixels: &[Color]) {
+        debug_assert!(pixels.len() >= (width * height) as usize);
+        self.blit_with(x, y, width, height, |i| pixels.get(i).map(|&c| c.into()));
+    }
+
+    /// Like [Screen::blit] but takes packed RGBA bytes (4 bytes per pixel).
+    pub fn blit_rgba8(&self, x: u32, y: u32, width: u32, height: u32, bytes: &[u8]) {
+        debug_assert!(bytes.len() >= (width * height * 4) as usize);
+        self.blit_with(x, y, width, height, |i| {
+            bytes
+                .get(i * 4..i * 4 + 4)
+                .map(|p| u32::from_ne_bytes([p[0], p[1], p[2], p[3]]))
+        });
+    }
+
+    fn blit_with(
+        &self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        pixel: impl Fn(usize) -> Option<u32>,
+    ) {
+        let (max_width, max_height) = (Display::width(), Display::height());
+        let cols = width.min(max_width.saturating_sub(x));
+        let rows = height.min(max_height.saturating_sub(y));
+
+        for row in 0..rows {
+            for col in 0..cols {
+                if let Some(color) = pixel((row * width + col) as usize) {
+                    unsafe {
+                        sys::OSScreenPutPixelEx(Display::id(), x + col, y + row, color);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Draw a straight line between two points.
+    ///
+    /// Parts of the line outside of the display are skipped.
+    pub fn line<X: Position, Y: Position>(&self, from: (X, Y), to: (X, Y), color: Color) {
+        let (width, height) = (Display::width(), Display::height());
+        let color: u32 = color.into();
+
+        let (mut x0, mut y0) = (from.0.into(width) as i32, from.1.into(height) as i32);
+        let (x1, y1) = (to.0.into(width) as i32, to.1.into(height) as i32);
+
+        // Bresenham's line algorithm
+        let dx = (x1 - x0).abs();
+        let dy = -(y1 - y0).abs();
+        let sx = if x0 < x1 { 1 } else { -1 };
+        let sy = if y0 < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+
+        loop {
+            if x0 >= 0 && y0 >= 0 && (x0 as u32) < width && (y0 as u32) < height {
+                unsafe {
+                    sys::OSScreenPutPixelEx(Display::id(), x0 as u32, y0 as u32, color);
+                }
+            }
+
+            if x0 == x1 && y0 == y1 {
+                break;
+            }
+
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x0 += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y0 += sy;
+            }
+        }
+    }
+
+    /// Draw a rectangle with its top left corner at `(x, y)`.
+    ///
+    /// If `filled` is `false` only the outline is drawn. Parts of the rectangle outside of the display are skipped.
+    pub fn rect<X: Position, Y: Position>(
+        &self,
+        x: X,
+        y: Y,
+        w: X,
+        h: Y,
+        color: Color,
+        filled: bool,
+    ) {
+        let (width, height) = (Display::width(), Display::height());
+        let color: u32 = color.into();
+
+        let (x, y) = (x.into(width), y.into(height));
+        let (w, h) = (w.into(width), h.into(height));
+        if w == 0 || h == 0 || x >= width || y >= height {
+            return;
+        }
+
+        let x_end = x.saturating_add(w).min(width);
+        let y_end = y.saturating_add(h).min(height);
+
+        for row in y..y_end {
+            let edge_row = row == y || row == y.saturating_add(h - 1);
+            for col in x..x_end {
+                let edge_col = col == x || col == x.saturating_add(w - 1);
+                if filled || edge_row || edge_col {
+                    unsafe {
+                        sys::OSScreenPutPixelEx(Display::id(), col, row, color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Screen of the TV. Returns `None` if no TV is connected.
+///
+/// # Example
+///
+/// ```
+/// use wut::screen::{self, TextAlign};
+///
+/// let drc = screen::drc();
+/// // handheld-only play continues on the GamePad
+/// let tv = screen::tv();
+///
+/// drc.text("Hello", 0, 0, TextAlign::Left);
+/// if let Some(tv) = &tv {
+///     tv.text("Hello", 0, 0, TextAlign::Left);
+/// }
+/// ```
+pub fn tv() -> Option<Screen<TV>> {
+    TV::try_resolution().map(|_| tv_or_default())
 }
 
-pub fn tv() -> Screen<TV> {
+/// Screen of the TV, even if no TV is connected.
+///
+/// Without a TV, the configured resolution or 1280x720 is used, so the content can still be captured via [save_screenshot].
+pub fn tv_or_default() -> Screen<TV> {
     Screen {
         display: PhantomData,
         _resource: OSSCREEN.acquire(),
@@ -315,3 +654,40 @@ pub fn drc() -> Screen<DRC> {
         _resource: OSSCREEN.acquire(),
     }
 }
+
+/// Save the content of the TV screen as a BMP file.
+pub fn save_screenshot<P: AsRef<Path>>(path: P) -> Result<(), FilesystemError> {
+    let screen = tv_or_default();
+    let (width, height) = screen.resolution();
+    let pixels = screen.capture();
+
+    const HEADER_SIZE: u32 = 14 + 40;
+    let image_size = width * height * 4;
+
+    let mut bmp = Vec::with_capacity((HEADER_SIZE + image_size) as usize);
+    // file header
+    bmp.extend_from_slice(b"BM");
+    bmp.extend_from_slice(&(HEADER_SIZE + image_size).to_le_bytes());
+    bmp.extend_from_slice(&0u32.to_le_bytes());
+    bmp.extend_from_slice(&HEADER_SIZE.to_le_bytes());
+    // info header
+    bmp.extend_from_slice(&40u32.to_le_bytes());
+    bmp.extend_from_slice(&(width as i32).to_le_bytes());
+    bmp.extend_from_slice(&(height as i32).to_le_bytes());
+    bmp.extend_from_slice(&1u16.to_le_bytes());
+    bmp.extend_from_slice(&32u16.to_le_bytes());
+    bmp.extend_from_slice(&0u32.to_le_bytes());
+    bmp.extend_from_slice(&image_size.to_le_bytes());
+    bmp.extend_from_slice(&2835u32.to_le_bytes());
+    bmp.extend_from_slice(&2835u32.to_le_bytes());
+    bmp.extend_from_slice(&0u32.to_le_bytes());
+    bmp.extend_from_slice(&0u32.to_le_bytes());
+    // pixel data: BGRA, bottom-up
+    for row in pixels.chunks_exact((width * 4) as usize).rev() {
+        for p in row.chunks_exact(4) {
+            bmp.extend_from_slice(&[p[2], p[1], p[0], p[3]]);
+        }
+    }
+
+    fs::write(path, bmp)
+}
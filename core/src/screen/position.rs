@@ -8,64 +8,134 @@ pub enum TextAlign {
     Right,
 }
 
-#[derive(Debug, Default)]
+/// Coordinate along one axis of the text grid, resolved against the number of columns or rows of the display when drawing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Offset {
+    /// Cells from the left or top edge.
+    Start(u32),
+    /// Cells from the right or bottom edge. `End(1)` is the last column or row.
+    End(u32),
+    /// Fraction of the display, clamped to `0.0..=1.0`.
+    Fraction(f32),
+}
+
+impl Default for Offset {
+    fn default() -> Self {
+        Self::Start(0)
+    }
+}
+
+impl Position for Offset {
+    fn into(self, max_value: u32) -> u32 {
+        match self {
+            Offset::Start(cells) => cells,
+            Offset::End(cells) => max_value.saturating_sub(cells),
+            Offset::Fraction(fraction) => Position::into(fraction, max_value),
+        }
+    }
+}
+
+/// Position of a text on the screen, which adapts to the different number of columns and rows of the TV and the GamePad.
+///
+/// # Example
+///
+/// ```
+/// use wut::screen::{self, TextAlign, TextPosition};
+///
+/// let screen = screen::drc();
+/// screen.text_at("Paused", TextPosition::center());
+/// screen.text_at("10% from the top", TextPosition::percent(50.0, 10.0).align(TextAlign::Center));
+/// screen.text_at("Press + to continue", TextPosition::from_bottom(1));
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
 pub struct TextPosition {
-    pub column: u32,
-    pub row: u32,
+    pub column: Offset,
+    pub row: Offset,
     pub align: TextAlign,
 }
 
-impl Into<TextPosition> for (u32, u32) {
-    fn into(self) -> TextPosition {
-        TextPosition {
-            column: self.0,
-            row: self.1,
+impl TextPosition {
+    pub fn new(column: Offset, row: Offset) -> Self {
+        Self {
+            column,
+            row,
             align: TextAlign::default(),
         }
     }
+
+    /// Centered on the screen.
+    pub fn center() -> Self {
+        Self::new(Offset::Fraction(0.5), Offset::Fraction(0.5)).align(TextAlign::Center)
+    }
+
+    /// At `x` percent of the width and `y` percent of the height, each in `0.0..=100.0`.
+    pub fn percent(x: f32, y: f32) -> Self {
+        Self::new(Offset::Fraction(x / 100.0), Offset::Fraction(y / 100.0))
+    }
+
+    /// At the left edge, `rows` rows above the bottom. `from_bottom(1)` is the last row.
+    pub fn from_bottom(rows: u32) -> Self {
+        Self::new(Offset::Start(0), Offset::End(rows))
+    }
+
+    /// Set the alignment of the text relative to the column.
+    pub fn align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Column and row on a display with `columns` columns and `rows` rows.
+    pub fn resolve(&self, columns: u32, rows: u32) -> (u32, u32) {
+        (
+            Position::into(self.column, columns),
+            Position::into(self.row, rows),
+        )
+    }
+
+    /// Split `text` into lines and compute the column and row of each line on a display with `columns` columns and `rows` rows.
+    pub fn format<'a>(
+        &self,
+        text: &'a str,
+        columns: u32,
+        rows: u32,
+    ) -> impl Iterator<Item = (&'a str, u32, u32)> {
+        let (column, mut row) = self.resolve(columns, rows);
+        let align = self.align;
+
+        text.split('\n').map(move |line| {
+            let col = match align {
+                TextAlign::Left => column,
+                TextAlign::Center => column.saturating_sub(line.len() as u32 / 2),
+                TextAlign::Right => column.saturating_sub(line.len() as u32),
+            };
+            row += 1;
+            (line, col, row - 1)
+        })
+    }
 }
 
-impl Into<TextPosition> for (u32, u32, TextAlign) {
+impl Into<TextPosition> for (u32, u32) {
     fn into(self) -> TextPosition {
-        TextPosition {
-            column: self.0,
-            row: self.1,
-            align: self.2,
-        }
+        TextPosition::new(Offset::Start(self.0), Offset::Start(self.1))
     }
 }
 
-impl Into<TextPosition> for (f32, f32) {
+impl Into<TextPosition> for (u32, u32, TextAlign) {
     fn into(self) -> TextPosition {
-        todo!()
+        Into::<TextPosition>::into((self.0, self.1)).align(self.2)
     }
 }
 
-impl Into<TextPosition> for (f32, f32, TextAlign) {
+/// Fractions of the width and height in `0.0..=1.0`.
+impl Into<TextPosition> for (f32, f32) {
     fn into(self) -> TextPosition {
-        todo!()
+        TextPosition::new(Offset::Fraction(self.0), Offset::Fraction(self.1))
     }
 }
 
-impl TextPosition {
-    pub fn format<'a>(&self, text: &'a str) -> impl Iterator<Item = (&'a str, u32, u32)> {
-        let mut row = self.row;
-        let column = self.column;
-        let align = self.align;
-
-        text.split('\n')
-            .map(move |line| {
-                //
-                let col = match align {
-                    TextAlign::Left => column,
-                    TextAlign::Center => column.saturating_sub(line.len() as u32 / 2),
-                    TextAlign::Right => column.saturating_sub(line.len() as u32),
-                };
-                row += 1;
-                //
-                (line, col, row - 1)
-            })
-            .into_iter()
+impl Into<TextPosition> for (f32, f32, TextAlign) {
+    fn into(self) -> TextPosition {
+        Into::<TextPosition>::into((self.0, self.1)).align(self.2)
     }
 }
 
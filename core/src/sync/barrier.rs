@@ -0,0 +1,103 @@
+use core::cell::UnsafeCell;
+use wut_sys as sys;
+
+/// A barrier enables multiple threads to synchronize the beginning of some computation.
+///
+/// # Example
+///
+/// ```
+/// use wut::thread;
+/// use wut::sync::Barrier;
+///
+/// let barrier = Barrier::new(3);
+///
+/// thread::scope(|s| {
+///     for _ in 0..3 {
+///         s.spawn(|| {
+///             // phase 1
+///             barrier.wait();
+///             // phase 2
+///         })
+///         .unwrap();
+///     }
+/// });
+/// ```
+pub struct Barrier {
+    n: usize,
+    state: UnsafeCell<BarrierState>,
+    mutex: UnsafeCell<sys::OSMutex>,
+    cond: UnsafeCell<sys::OSCondition>,
+}
+
+/// Protected by `Barrier::mutex`.
+struct BarrierState {
+    count: usize,
+    generation: usize,
+}
+
+/// Returned by [Barrier::wait] when all threads in the barrier have rendezvoused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarrierWaitResult(bool);
+
+impl BarrierWaitResult {
+    /// Exactly one thread of each rendezvous is the leader.
+    #[inline]
+    pub fn is_leader(&self) -> bool {
+        self.0
+    }
+}
+
+unsafe impl Send for Barrier {}
+unsafe impl Sync for Barrier {}
+
+impl Barrier {
+    /// Create a new barrier that blocks a group of `n` threads.
+    ///
+    /// A barrier with `n` of `0` or `1` never blocks.
+    pub fn new(n: usize) -> Self {
+        let mut mutex = sys::OSMutex::default();
+        let mut cond = sys::OSCondition::default();
+        unsafe {
+            sys::OSInitMutex(&mut mutex);
+            sys::OSInitCond(&mut cond);
+        }
+
+        Self {
+            n,
+            state: UnsafeCell::new(BarrierState {
+                count: 0,
+                generation: 0,
+            }),
+            mutex: UnsafeCell::new(mutex),
+            cond: UnsafeCell::new(cond),
+        }
+    }
+
+    /// Block the current thread until `n` threads have called `wait`, then release all of them at once.
+    ///
+    /// The barrier is reusable afterwards.
+    pub fn wait(&self) -> BarrierWaitResult {
+        unsafe {
+            sys::OSLockMutex(self.mutex.get());
+
+            let generation = (*self.state.get()).generation;
+            (*self.state.get()).count += 1;
+
+            let leader = if (*self.state.get()).count < self.n {
+                while generation == (*self.state.get()).generation {
+                    sys::OSWaitCond(self.cond.get(), self.mutex.get());
+                }
+                false
+            } else {
+                (*self.state.get()).count = 0;
+                (*self.state.get()).generation = generation.wrapping_add(1);
+                sys::OSSignalCond(self.cond.get());
+                true
+            };
+
+            sys::OSUnlockMutex(self.mutex.get());
+
+            BarrierWaitResult(leader)
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{
+    sync::{MutexError, MutexGuard},
+    time::{Duration, Instant, SystemTime},
+};
+use core::{cell::UnsafeCell, ffi};
+use wut_sys as sys;
+
+/// Condition variable
+///
+/// Blocks a thread while waiting for an event, paired with a [Mutex][super::Mutex]. Usage similar to std::sync::Condvar.
+///
+/// CafeOS always wakes every thread waiting on a condition, so [Condvar::notify_one] behaves like [Condvar::notify_all]. As with any condition variable, waits should be performed in a loop which checks the actual condition.
+pub struct Condvar(UnsafeCell<sys::OSCondition>);
+
+/// Whether a [Condvar::wait_timeout] returned because of a timeout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaitTimeoutResult(bool);
+
+impl WaitTimeoutResult {
+    #[inline]
+    pub fn timed_out(&self) -> bool {
+        self.0
+    }
+}
+
+unsafe impl Send for Condvar {}
+unsafe impl Sync for Condvar {}
+
+impl Condvar {
+    /// Create a new condition variable.
+    pub fn new() -> Self {
+        let mut cond = sys::OSCondition::default();
+        unsafe {
+            sys::OSInitCond(&mut cond);
+        }
+        Self(UnsafeCell::new(cond))
+    }
+
+    /// Atomically unlock the mutex of `guard` and block until this condition variable is notified. The mutex is locked again before returning.
+    pub fn wait<'a, T>(&self, guard: MutexGuard<'a, T>) -> Result<MutexGuard<'a, T>, MutexError> {
+        unsafe {
+            sys::OSWaitCond(self.0.get(), guard.raw());
+        }
+        Ok(guard)
+    }
+
+    /// Like [Condvar::wait], but gives up after `timeout`.
+    ///
+    /// The timeout is implemented with an OS alarm which notifies the condition variable, so other waiters may observe a spurious wakeup.
+    pub fn wait_timeout<'a, T>(
+        &self,
+        guard: MutexGuard<'a, T>,
+        timeout: Duration,
+    ) -> Result<(MutexGuard<'a, T>, WaitTimeoutResult), MutexError> {
+        let start = Instant::now();
+        let mut alarm = sys::OSAlarm::default();
+
+        unsafe {
+            sys::OSCreateAlarm(&mut alarm);
+            sys::OSSetAlarmUserData(&mut alarm, self.0.get() as *mut ffi::c_void);
+            sys::OSSetAlarm(
+                &mut alarm,
+                Into::<SystemTime>::into(timeout).into(),
+                Some(alarm_callback),
+            );
+
+            sys::OSWaitCond(self.0.get(), guard.raw());
+
+            sys::OSCancelAlarm(&mut alarm);
+        }
+
+        let timed_out = start.elapsed() >= timeout;
+        Ok((guard, WaitTimeoutResult(timed_out)))
+    }
+
+    /// Wake up a thread blocked on this condition variable.
+    ///
+    /// CafeOS wakes all waiting threads, see [Condvar].
+    #[inline]
+    pub fn notify_one(&self) {
+        self.notify_all();
+    }
+
+    /// Wake up all threads blocked on this condition variable.
+    #[inline]
+    pub fn notify_all(&self) {
+        unsafe {
+            sys::OSSignalCond(self.0.get());
+        }
+    }
+}
+
+impl Default for Condvar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Notify the condition variable stored in the alarm's user data.
+unsafe extern "C" fn alarm_callback(alarm: *mut sys::OSAlarm, _context: *mut sys::OSContext) {
+    unsafe {
+        let cond = sys::OSGetAlarmUserData(alarm) as *mut sys::OSCondition;
+        sys::OSSignalCond(cond);
+    }
+}
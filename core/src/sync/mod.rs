@@ -1,22 +1,30 @@
 //! Useful synchronization primitives.
 
+pub use alloc::sync::{Arc, Weak};
 pub use core::sync::*;
 
+mod barrier;
+mod condvar;
 mod event;
 mod lazy_lock;
 pub mod mpmc;
 pub mod mpsc;
 mod mutex;
+mod once;
 mod once_lock;
 mod rwlock;
 mod semaphore;
 
+pub use barrier::{Barrier, BarrierWaitResult};
+pub use condvar::{Condvar, WaitTimeoutResult};
 pub use event::{AutoEvent, ManualEvent};
 pub use lazy_lock::LazyLock;
-pub use mutex::{Mutex, MutexError};
+pub use mpsc::channel;
+pub use mutex::{Mutex, MutexError, MutexGuard};
+pub use once::Once;
 pub use once_lock::OnceLock;
-// pub use rwlock::RwLock;
-pub use semaphore::Semaphore;
+pub use rwlock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+pub use semaphore::{Semaphore, SemaphoreGuard};
 
 /// Trickery for constant mutex
 pub type ConstMutex<T> = LazyLock<Mutex<T>>;
@@ -1,33 +1,216 @@
-use super::mpmc;
+//! Multi-producer, single-consumer FIFO queue communication primitives.
 
+use crate::{
+    sync::{AutoEvent, Mutex},
+    time::{Duration, Instant},
+};
+use alloc::{collections::VecDeque, sync::Arc};
+use core::{error, fmt};
+use thiserror::Error;
+
+/// Create a new unbounded channel.
+///
+/// Sending never blocks. The [Receiver] blocks until a message is available or all [Sender]s were dropped.
+///
+/// # Example
+///
+/// ```
+/// use wut::{sync::mpsc, thread};
+///
+/// let (tx, rx) = mpsc::channel();
+///
+/// thread::spawn(move || tx.send(42).unwrap()).unwrap();
+///
+/// assert_eq!(rx.recv().unwrap(), 42);
+/// ```
 pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
-    let (tx, rx) = mpmc::channel();
-    (Sender { inner: tx }, Receiver { inner: rx })
+    new(None)
+}
+
+/// Create a new bounded channel which holds at most `bound` messages.
+///
+/// [Sender::send] blocks while the channel is full. A `bound` of `0` is treated as `1`.
+pub fn sync_channel<T>(bound: usize) -> (Sender<T>, Receiver<T>) {
+    new(Some(bound.max(1)))
+}
+
+fn new<T>(capacity: Option<usize>) -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue: VecDeque::new(),
+            senders: 1,
+            receiver: true,
+        }),
+        available: AutoEvent::new(),
+        space: AutoEvent::new(),
+        capacity,
+    });
+
+    (
+        Sender {
+            shared: Arc::clone(&shared),
+        },
+        Receiver { shared },
+    )
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ChannelError {
+    #[error("All senders of the channel were dropped")]
+    Disconnected,
+    #[error("The channel is currently empty")]
+    Empty,
+    #[error("Timed out waiting on the channel")]
+    Timeout,
+}
+
+/// Returned by [Sender::send] if the [Receiver] was dropped. Contains the message which could not be sent.
+#[derive(PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SendError { .. }")
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("The receiver of the channel was dropped")
+    }
+}
+
+impl<T> error::Error for SendError<T> {}
+
+struct State<T> {
+    queue: VecDeque<T>,
+    senders: usize,
+    receiver: bool,
 }
 
-#[derive(Clone)]
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    /// Signaled when a message was pushed or the last sender was dropped.
+    available: AutoEvent,
+    /// Signaled when a message was popped or the receiver was dropped.
+    space: AutoEvent,
+    capacity: Option<usize>,
+}
+
+/// The sending half of a channel. Can be cloned to send from multiple threads.
 pub struct Sender<T> {
-    inner: mpmc::Sender<T>,
+    shared: Arc<Shared<T>>,
 }
 
 impl<T> Sender<T> {
-    pub fn send(&self, data: T) -> Result<(), ()> {
-        self.inner.send(data)
+    /// Send a message to the [Receiver].
+    ///
+    /// Blocks while a bounded channel is full. Fails if the receiver was dropped.
+    pub fn send(&self, data: T) -> Result<(), SendError<T>> {
+        loop {
+            let mut state = self.shared.state.lock().unwrap();
+
+            if !state.receiver {
+                return Err(SendError(data));
+            }
+
+            if self
+                .shared
+                .capacity
+                .is_none_or(|capacity| state.queue.len() < capacity)
+            {
+                state.queue.push_back(data);
+                drop(state);
+                self.shared.available.signal();
+                return Ok(());
+            }
+
+            drop(state);
+            self.shared.space.wait();
+        }
     }
 }
 
-unsafe impl<T> Sync for Sender<T> {}
-unsafe impl<T: Send> Send for Sender<T> {}
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.state.lock().unwrap().senders += 1;
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.senders -= 1;
+        if state.senders == 0 {
+            drop(state);
+            self.shared.available.signal();
+        }
+    }
+}
 
+/// The receiving half of a channel.
 pub struct Receiver<T> {
-    inner: mpmc::Receiver<T>,
+    shared: Arc<Shared<T>>,
 }
 
 impl<T> Receiver<T> {
-    pub fn recv(&self) -> Result<T, ()> {
-        self.inner.recv()
+    /// Receive a message without blocking.
+    ///
+    /// Returns [ChannelError::Empty] if no message is available and [ChannelError::Disconnected] if additionally all senders were dropped.
+    pub fn try_recv(&self) -> Result<T, ChannelError> {
+        let mut state = self.shared.state.lock().unwrap();
+
+        match state.queue.pop_front() {
+            Some(data) => {
+                drop(state);
+                self.shared.space.signal();
+                Ok(data)
+            }
+            None if state.senders == 0 => Err(ChannelError::Disconnected),
+            None => Err(ChannelError::Empty),
+        }
+    }
+
+    /// Block until a message is available.
+    ///
+    /// Returns [ChannelError::Disconnected] once the channel is empty and all senders were dropped.
+    pub fn recv(&self) -> Result<T, ChannelError> {
+        loop {
+            match self.try_recv() {
+                Err(ChannelError::Empty) => self.shared.available.wait(),
+                result => return result,
+            }
+        }
+    }
+
+    /// Block until a message is available or `timeout` has passed.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, ChannelError> {
+        let start = Instant::now();
+
+        loop {
+            match self.try_recv() {
+                Err(ChannelError::Empty) => {
+                    let remaining = timeout.saturating_sub(start.elapsed());
+                    if remaining.is_zero() {
+                        return Err(ChannelError::Timeout);
+                    }
+                    self.shared.available.wait_timeout(remaining);
+                }
+                result => return result,
+            }
+        }
     }
 }
 
-unsafe impl<T> Sync for Receiver<T> {}
-unsafe impl<T: Send> Send for Receiver<T> {}
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.receiver = false;
+        state.queue.clear();
+        drop(state);
+        self.shared.space.signal_all();
+    }
+}
@@ -73,6 +73,13 @@ impl<T> Mutex<T> {
     }
 }
 
+impl<'a, T> MutexGuard<'a, T> {
+    /// The locked mutex. Used by [Condvar][super::Condvar] to release and reacquire the lock.
+    pub(crate) fn raw(&self) -> *mut sys::OSMutex {
+        self.mutex.mutex.get()
+    }
+}
+
 impl<T> Deref for MutexGuard<'_, T> {
     type Target = T;
 
@@ -0,0 +1,96 @@
+use core::{
+    cell::UnsafeCell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use wut_sys as sys;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OnceState {
+    Incomplete,
+    Running,
+    Complete,
+}
+
+/// A synchronization primitive which can be used to run a one-time initialization.
+///
+/// Unlike [OnceLock][super::OnceLock], `Once` does not store a value and guarantees that concurrent callers block until the initialization has finished.
+///
+/// As the underlying mutex must be registerd by the OS, `Once` cannot be created with a `const` function.
+pub struct Once {
+    completed: AtomicBool,
+    state: UnsafeCell<OnceState>,
+    mutex: UnsafeCell<sys::OSMutex>,
+    cond: UnsafeCell<sys::OSCondition>,
+}
+
+unsafe impl Send for Once {}
+unsafe impl Sync for Once {}
+
+impl Once {
+    pub fn new() -> Self {
+        let mut mutex = sys::OSMutex::default();
+        let mut cond = sys::OSCondition::default();
+        unsafe {
+            sys::OSInitMutex(&mut mutex);
+            sys::OSInitCond(&mut cond);
+        }
+
+        Self {
+            completed: AtomicBool::new(false),
+            state: UnsafeCell::new(OnceState::Incomplete),
+            mutex: UnsafeCell::new(mutex),
+            cond: UnsafeCell::new(cond),
+        }
+    }
+
+    /// Run `f` exactly once, no matter how often or from how many threads this is called.
+    ///
+    /// If another thread is currently running its closure, this blocks until it has finished. Calling `call_once` on the same `Once` from within `f` deadlocks.
+    pub fn call_once<F>(&self, f: F)
+    where
+        F: FnOnce(),
+    {
+        if self.is_completed() {
+            return;
+        }
+
+        unsafe {
+            sys::OSLockMutex(self.mutex.get());
+
+            loop {
+                match *self.state.get() {
+                    OnceState::Complete => break,
+                    OnceState::Running => {
+                        sys::OSWaitCond(self.cond.get(), self.mutex.get());
+                    }
+                    OnceState::Incomplete => {
+                        *self.state.get() = OnceState::Running;
+                        sys::OSUnlockMutex(self.mutex.get());
+
+                        f();
+
+                        sys::OSLockMutex(self.mutex.get());
+                        *self.state.get() = OnceState::Complete;
+                        self.completed.store(true, Ordering::Release);
+                        sys::OSSignalCond(self.cond.get());
+                        break;
+                    }
+                }
+            }
+
+            sys::OSUnlockMutex(self.mutex.get());
+        }
+    }
+
+    /// Returns `true` if some [Once::call_once] has completed successfully.
+    #[inline]
+    pub fn is_completed(&self) -> bool {
+        self.completed.load(Ordering::Acquire)
+    }
+}
+
+impl Default for Once {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,98 +1,241 @@
-/*
-use core::cell::UnsafeCell;
-use core::sync::atomic::{AtomicUsize, AtomicBool, Ordering};
-
+use crate::sync::MutexError;
+use core::{
+    cell::UnsafeCell,
+    ops::{Deref, DerefMut},
+};
+use wut_sys as sys;
+
+/// Reader-writer lock
+///
+/// Allows any number of readers or a single writer at a time. Waiting writers are preferred over new readers, so a steady stream of readers cannot starve a writer.
+///
+/// Usage similar to std::sync::RwLock
+///
+/// # Example
+///
+/// ```
+/// use wut::sync::RwLock;
+///
+/// let lock = RwLock::new(5);
+///
+/// {
+///     let a = lock.read().unwrap();
+///     let b = lock.read().unwrap();
+///     assert_eq!(*a + *b, 10);
+/// }
+///
+/// *lock.write().unwrap() += 1;
+/// assert_eq!(*lock.read().unwrap(), 6);
+/// ```
+///
+/// Readers never observe a half finished write:
+///
+/// ```
+/// use wut::{sync::RwLock, thread, time::Duration};
+///
+/// let lock = RwLock::new((0, 0));
+///
+/// thread::scope(|s| {
+///     for _ in 0..4 {
+///         s.spawn(|| {
+///             for _ in 0..100 {
+///                 let pair = lock.read().unwrap();
+///                 assert_eq!(pair.0, pair.1);
+///             }
+///         })
+///         .unwrap();
+///     }
+///
+///     s.spawn(|| {
+///         for _ in 0..100 {
+///             let mut pair = lock.write().unwrap();
+///             pair.0 += 1;
+///             thread::sleep(Duration::from_millis(1));
+///             pair.1 += 1;
+///         }
+///     })
+///     .unwrap();
+/// });
+///
+/// assert_eq!(*lock.read().unwrap(), (100, 100));
+/// ```
 pub struct RwLock<T> {
-    readers: AtomicUsize,
-    writer: AtomicBool,
-    data: UnsafeCell<T>,
+    inner: UnsafeCell<T>,
+    mutex: UnsafeCell<sys::OSMutex>,
+    cond: UnsafeCell<sys::OSCondition>,
+    state: UnsafeCell<LockState>,
+}
+
+/// Protected by `RwLock::mutex`.
+#[derive(Default)]
+struct LockState {
+    readers: usize,
+    writer: bool,
+    waiting_writers: usize,
+}
+
+pub struct RwLockReadGuard<'a, T> {
+    lock: &'a RwLock<T>,
+}
+
+pub struct RwLockWriteGuard<'a, T> {
+    lock: &'a RwLock<T>,
 }
 
 unsafe impl<T: Send> Send for RwLock<T> {}
-unsafe impl<T: Send> Sync for RwLock<T> {}
+unsafe impl<T: Send + Sync> Sync for RwLock<T> {}
 
 impl<T> RwLock<T> {
-    /// Creates a new `RwLock`.
-    pub const fn new(data: T) -> Self {
-        RwLock {
-            readers: AtomicUsize::new(0),
-            writer: AtomicBool::new(false),
-            data: UnsafeCell::new(data),
+    /// Create a new reader-writer lock
+    ///
+    /// As the underlying mutex must be registerd by the OS, this cannot be a `const` function.
+    pub fn new(inner: T) -> Self {
+        let mut mutex = sys::OSMutex::default();
+        let mut cond = sys::OSCondition::default();
+        unsafe {
+            sys::OSInitMutex(&mut mutex);
+            sys::OSInitCond(&mut cond);
+        }
+
+        Self {
+            inner: UnsafeCell::new(inner),
+            mutex: UnsafeCell::new(mutex),
+            cond: UnsafeCell::new(cond),
+            state: UnsafeCell::new(LockState::default()),
         }
     }
 
-    /// Acquires a read lock.
-    pub fn read(&self) -> RwLockReadGuard<T> {
-        while self.writer.load(Ordering::SeqCst) {
-            // Busy-wait while a writer holds the lock
-            // core::hint::spin_loop();
+    /// Lock with shared read access, blocking the current thread until it can be acquired.
+    pub fn read(&self) -> Result<RwLockReadGuard<T>, MutexError> {
+        self.lock_state();
+        while self.state().writer || self.state().waiting_writers > 0 {
+            self.wait();
         }
-        self.readers.fetch_add(1, Ordering::SeqCst);
+        self.state().readers += 1;
+        self.unlock_state();
 
-        RwLockReadGuard { lock: self }
+        Ok(RwLockReadGuard { lock: self })
     }
 
-    /// Acquires a write lock.
-    pub fn write(&self) -> RwLockWriteGuard<T> {
-        while self.readers.load(Ordering::SeqCst) > 0 || self.writer.load(Ordering::SeqCst) {
-            // Busy-wait while readers exist or a writer holds the lock
-            // core::hint::spin_loop();
+    /// Try to lock with shared read access without blocking.
+    pub fn try_read(&self) -> Result<RwLockReadGuard<T>, MutexError> {
+        self.lock_state();
+        let state = self.state();
+        let result = if state.writer || state.waiting_writers > 0 {
+            Err(MutexError::AlreadyLocked)
+        } else {
+            state.readers += 1;
+            Ok(RwLockReadGuard { lock: self })
+        };
+        self.unlock_state();
+
+        result
+    }
+
+    /// Lock with exclusive write access, blocking the current thread until it can be acquired.
+    pub fn write(&self) -> Result<RwLockWriteGuard<T>, MutexError> {
+        self.lock_state();
+        self.state().waiting_writers += 1;
+        while self.state().writer || self.state().readers > 0 {
+            self.wait();
         }
-        self.writer.store(true, Ordering::SeqCst);
+        let state = self.state();
+        state.waiting_writers -= 1;
+        state.writer = true;
+        self.unlock_state();
 
-        RwLockWriteGuard { lock: self }
+        Ok(RwLockWriteGuard { lock: self })
     }
 
-    fn release_read(&self) {
-        self.readers.fetch_sub(1, Ordering::SeqCst);
+    /// Try to lock with exclusive write access without blocking.
+    pub fn try_write(&self) -> Result<RwLockWriteGuard<T>, MutexError> {
+        self.lock_state();
+        let state = self.state();
+        let result = if state.writer || state.readers > 0 {
+            Err(MutexError::AlreadyLocked)
+        } else {
+            state.writer = true;
+            Ok(RwLockWriteGuard { lock: self })
+        };
+        self.unlock_state();
+
+        result
     }
 
-    fn release_write(&self) {
-        self.writer.store(false, Ordering::SeqCst);
+    /// Access the lock state. Must only be used while the internal mutex is held and not across [RwLock::wait].
+    #[inline]
+    fn state(&self) -> &mut LockState {
+        unsafe { &mut *self.state.get() }
     }
-}
 
-/// RAII guard for read access.
-pub struct RwLockReadGuard<'a, T> {
-    lock: &'a RwLock<T>,
-}
+    #[inline]
+    fn lock_state(&self) {
+        unsafe {
+            sys::OSLockMutex(self.mutex.get());
+        }
+    }
 
-impl<T> Drop for RwLockReadGuard<'_, T> {
-    fn drop(&mut self) {
-        self.lock.release_read();
+    #[inline]
+    fn unlock_state(&self) {
+        unsafe {
+            sys::OSUnlockMutex(self.mutex.get());
+        }
+    }
+
+    /// Wait for the state to change. Must be called with the internal mutex held.
+    fn wait(&self) {
+        unsafe {
+            sys::OSWaitCond(self.cond.get(), self.mutex.get());
+        }
+    }
+
+    /// Wake all waiting threads. Must be called with the internal mutex held.
+    fn notify(&self) {
+        unsafe {
+            sys::OSSignalCond(self.cond.get());
+        }
     }
 }
 
-impl<T> core::ops::Deref for RwLockReadGuard<'_, T> {
+impl<T> Deref for RwLockReadGuard<'_, T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
-        unsafe { &*self.lock.data.get() }
+        unsafe { &*self.lock.inner.get() }
     }
 }
 
-/// RAII guard for write access.
-pub struct RwLockWriteGuard<'a, T> {
-    lock: &'a RwLock<T>,
-}
-
-impl<T> Drop for RwLockWriteGuard<'_, T> {
+impl<T> Drop for RwLockReadGuard<'_, T> {
     fn drop(&mut self) {
-        self.lock.release_write();
+        self.lock.lock_state();
+        let state = self.lock.state();
+        state.readers -= 1;
+        if state.readers == 0 {
+            self.lock.notify();
+        }
+        self.lock.unlock_state();
     }
 }
 
-impl<T> core::ops::Deref for RwLockWriteGuard<'_, T> {
+impl<T> Deref for RwLockWriteGuard<'_, T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
-        unsafe { &*self.lock.data.get() }
+        unsafe { &*self.lock.inner.get() }
     }
 }
 
-impl<T> core::ops::DerefMut for RwLockWriteGuard<'_, T> {
+impl<T> DerefMut for RwLockWriteGuard<'_, T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        unsafe { &mut *self.lock.data.get() }
+        unsafe { &mut *self.lock.inner.get() }
+    }
+}
+
+impl<T> Drop for RwLockWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.lock_state();
+        self.lock.state().writer = false;
+        self.lock.notify();
+        self.lock.unlock_state();
     }
 }
-*/
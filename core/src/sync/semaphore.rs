@@ -1,6 +1,24 @@
 use core::cell::UnsafeCell;
 use wut_sys as sys;
 
+/// A counting semaphore.
+///
+/// Limits how many threads may use a resource at the same time. This complements [Rrc][crate::rrc::Rrc], which only keeps a resource initialized while it is in use, but does not restrict concurrent access to it.
+///
+/// # Example
+///
+/// ```
+/// use wut::sync::{LazyLock, Semaphore};
+///
+/// // at most 2 concurrent downloads
+/// static DOWNLOADS: LazyLock<Semaphore> = LazyLock::new(|| Semaphore::new(2));
+///
+/// fn download(url: &str) {
+///     let _permit = DOWNLOADS.acquire();
+///     let response = wut::net::http::get(url);
+///     // ...
+/// } // permit is released here
+/// ```
 pub struct Semaphore(UnsafeCell<sys::OSSemaphore>);
 
 impl Semaphore {
@@ -38,7 +56,35 @@ impl Semaphore {
     pub fn try_wait(&self) -> i32 {
         unsafe { sys::OSTryWaitSemaphore(self.inner()) }
     }
+
+    /// Block until a permit is available and take it. The permit is returned when the guard is dropped.
+    pub fn acquire(&self) -> SemaphoreGuard<'_> {
+        self.wait();
+        SemaphoreGuard { semaphore: self }
+    }
+
+    /// Take a permit if one is available without blocking.
+    pub fn try_acquire(&self) -> Option<SemaphoreGuard<'_>> {
+        // returns the count before decrementing, which is only done if it was positive
+        if self.try_wait() > 0 {
+            Some(SemaphoreGuard { semaphore: self })
+        } else {
+            None
+        }
+    }
 }
 
 unsafe impl Send for Semaphore {}
 unsafe impl Sync for Semaphore {}
+
+/// A permit of a [Semaphore]. Released on drop.
+#[must_use = "if unused the permit will immediately be released"]
+pub struct SemaphoreGuard<'a> {
+    semaphore: &'a Semaphore,
+}
+
+impl Drop for SemaphoreGuard<'_> {
+    fn drop(&mut self) {
+        self.semaphore.signal();
+    }
+}
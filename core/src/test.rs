@@ -0,0 +1,189 @@
+//! On-console test harness
+//!
+//! `cargo test` cannot run on the Wii U, so tests are compiled into a normal application instead. Functions marked with `#[wut::test]` become [Test]s, which register themselves before `main` runs. [test_main!][crate::test_main] runs all registered tests. Progress and results are written to the [logger][crate::logger], so enable the `Console` channel to see them on screen or `Udp` to receive them on a PC.
+//!
+//! A test fails if it returns an `Err`. Panics cannot be caught, as the panic handler aborts the application. Since every test is announced before it runs, the last announced test is the one which panicked.
+//!
+//! # Example
+//!
+//! ```
+//! #![no_std]
+//! #![no_main]
+//!
+//! use wut::path::Path;
+//!
+//! #[wut::test]
+//! fn join() {
+//!     assert_eq!(Path::new("/vol").join("content"), Path::new("/vol/content"));
+//! }
+//!
+//! #[wut::test]
+//! fn read_dir() -> Result<(), wut::fs::FilesystemError> {
+//!     wut::fs::read_dir("/vol/content")?;
+//!     Ok(())
+//! }
+//!
+//! #[wut::main(Console, Udp)]
+//! fn main() {
+//!     let summary = wut::test_main!();
+//!
+//!     while wut::process::running() {}
+//! }
+//! ```
+
+use crate::logger;
+use alloc::{format, string::String, vec::Vec};
+use core::{
+    fmt::Debug,
+    ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+/// Head of the intrusive list of all registered tests.
+static REGISTRY: AtomicPtr<Registration> = AtomicPtr::new(ptr::null_mut());
+
+/// A single test. Created by `#[wut::test]`.
+#[derive(Debug, Clone, Copy)]
+pub struct Test {
+    /// Full path of the test function.
+    pub name: &'static str,
+    pub run: fn() -> Result<(), String>,
+}
+
+/// Entry of the test registry. Created by `#[wut::test]`.
+///
+/// The attribute places a constructor into `.init_array`, which calls [register][Registration::register] on a static `Registration` before `main` runs.
+#[doc(hidden)]
+pub struct Registration {
+    test: Test,
+    next: AtomicPtr<Registration>,
+}
+
+impl Registration {
+    pub const fn new(test: Test) -> Self {
+        Self {
+            test,
+            next: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    pub fn register(&'static self) {
+        let this = self as *const Self as *mut Self;
+        let mut head = REGISTRY.load(Ordering::Acquire);
+        loop {
+            self.next.store(head, Ordering::Relaxed);
+            match REGISTRY.compare_exchange_weak(head, this, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+}
+
+/// All tests marked with `#[wut::test]`, sorted by name.
+pub fn tests() -> Vec<Test> {
+    let mut tests = Vec::new();
+    let mut next = REGISTRY.load(Ordering::Acquire);
+
+    // SAFETY: only `&'static Registration`s are ever inserted
+    while let Some(registration) = unsafe { next.as_ref() } {
+        tests.push(registration.test);
+        next = registration.next.load(Ordering::Acquire);
+    }
+
+    tests.sort_by_key(|test| test.name);
+    tests
+}
+
+/// Return types of test functions.
+pub trait TestResult {
+    /// Convert into `Ok` on success or into an error message on failure.
+    fn into_result(self) -> Result<(), String>;
+}
+
+impl TestResult for () {
+    fn into_result(self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl<E: Debug> TestResult for Result<(), E> {
+    fn into_result(self) -> Result<(), String> {
+        self.map_err(|e| format!("{e:?}"))
+    }
+}
+
+/// Outcome of a test run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+}
+
+impl Summary {
+    /// Check if all tests passed.
+    #[inline]
+    pub fn success(&self) -> bool {
+        self.failed == 0
+    }
+}
+
+/// Run all `tests` in order and report the results to the logger.
+///
+/// Usually called via [test_main!][crate::test_main].
+pub fn run(tests: &[Test]) -> Summary {
+    let mut summary = Summary::default();
+
+    report(&format!("running {} tests", tests.len()));
+
+    for test in tests {
+        report(&format!("test {} ...", test.name));
+
+        match (test.run)() {
+            Ok(()) => {
+                summary.passed += 1;
+                report(&format!("test {} ... ok", test.name));
+            }
+            Err(e) => {
+                summary.failed += 1;
+                report(&format!("test {} ... FAILED: {e}", test.name));
+            }
+        }
+    }
+
+    report(&format!(
+        "test result: {}. {} passed; {} failed",
+        if summary.success() { "ok" } else { "FAILED" },
+        summary.passed,
+        summary.failed
+    ));
+
+    summary
+}
+
+/// Results are reported even if no logger channel is initialized.
+fn report(text: &str) {
+    let _ = logger::print(text);
+}
+
+/// Run all `#[wut::test]` functions and return a [Summary][crate::test::Summary].
+///
+/// Without arguments, every [registered][crate::test::tests] test is run. Listing tests runs only those, in the given order.
+///
+/// # Example
+///
+/// ```
+/// let summary = wut::test_main!();
+///
+/// // only a subset
+/// let summary = wut::test_main!(tests::parse, tests::render);
+/// ```
+#[macro_export]
+macro_rules! test_main {
+    () => {
+        $crate::test::run(&$crate::test::tests())
+    };
+    ($($test:path),+ $(,)?) => {
+        $crate::test::run(&[$($test),*])
+    };
+}
@@ -1,8 +1,8 @@
 use crate::thread::{
-    JoinHandle,
+    JoinHandle, Packet, Scope, ScopedJoinHandle,
     thread::{Thread, ThreadAttribute, ThreadError},
 };
-use alloc::{alloc::Layout, boxed::Box, string::String};
+use alloc::{alloc::Layout, boxed::Box, string::String, sync::Arc};
 use core::{alloc::GlobalAlloc, ffi};
 use flagset::FlagSet;
 use wut_sys as sys;
@@ -14,6 +14,7 @@ pub struct Builder {
     priority: i32,
     stack_size: usize,
     quantum: u32,
+    core: Option<u8>,
 }
 
 impl Default for Builder {
@@ -24,6 +25,7 @@ impl Default for Builder {
             priority: 15,
             stack_size: 128 * 1024,
             quantum: 100_000, // ticks
+            core: None,
         }
     }
 }
@@ -64,25 +66,84 @@ impl Builder {
         self
     }
 
-    pub fn spawn<F>(self, f: F) -> Result<JoinHandle, ThreadError>
+    /// Pin the thread to a single CPU core (`0`, `1` or `2`).
+    ///
+    /// Overrides any affinity set via [Builder::attribute].
+    pub fn core(mut self, core: u8) -> Self {
+        self.core = Some(core);
+        self
+    }
+
+    pub fn spawn<F, T>(self, f: F) -> Result<JoinHandle<T>, ThreadError>
     where
-        F: FnOnce() + Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
     {
         unsafe { self.spawn_unchecked(f) }
     }
 
-    pub unsafe fn spawn_unchecked<F>(self, f: F) -> Result<JoinHandle, ThreadError>
+    pub unsafe fn spawn_unchecked<F, T>(self, f: F) -> Result<JoinHandle<T>, ThreadError>
     where
-        F: FnOnce() + Send,
+        F: FnOnce() -> T + Send,
+        T: Send,
     {
-        Ok(JoinHandle::new(unsafe { self.spawn_unchecked_(f)? }))
+        let packet = Arc::new(Packet::new());
+        let their_packet = packet.clone();
+
+        let main = move || {
+            let result = f();
+            unsafe { their_packet.set(result) };
+        };
+
+        Ok(JoinHandle::new(unsafe { self.spawn_unchecked_(main)? }, packet))
+    }
+
+    /// Spawn a new thread within `scope`. See [Scope::spawn].
+    pub fn spawn_scoped<'scope, 'env, F, T>(
+        self,
+        scope: &'scope Scope<'scope, 'env>,
+        f: F,
+    ) -> Result<ScopedJoinHandle<'scope, T>, ThreadError>
+    where
+        F: FnOnce() -> T + Send + 'scope,
+        T: Send + 'scope,
+    {
+        let packet = Arc::new(Packet::new());
+        let their_packet = packet.clone();
+        let data = scope.data().clone();
+
+        // the result must be stored (or dropped) before the scope is allowed to end
+        let main = move || {
+            unsafe { their_packet.set(f()) };
+            drop(their_packet);
+            data.decrement();
+        };
+
+        scope.data().increment();
+        match unsafe { self.spawn_unchecked_(main) } {
+            Ok(thread) => Ok(ScopedJoinHandle::new(JoinHandle::new(thread, packet))),
+            Err(e) => {
+                scope.data().decrement();
+                Err(e)
+            }
+        }
     }
 
     /// There 100% is a better and more rusty way of doing this, but it works for now
-    unsafe fn spawn_unchecked_<F>(self, f: F) -> Result<Thread, ThreadError>
+    unsafe fn spawn_unchecked_<F>(mut self, f: F) -> Result<Thread, ThreadError>
     where
         F: FnOnce() + Send,
     {
+        if let Some(core) = self.core {
+            let affinity = match core {
+                0 => ThreadAttribute::Cpu0,
+                1 => ThreadAttribute::Cpu1,
+                2 => ThreadAttribute::Cpu2,
+                _ => return Err(ThreadError::InvalidCore(core)),
+            };
+            self.attribute = (self.attribute - ThreadAttribute::CpuAny) | affinity;
+        }
+
         let layout = Layout::new::<sys::OSThread>();
         let thread = unsafe { GLOBAL_ALLOCATOR.alloc_zeroed(layout) } as *mut sys::OSThread;
         if thread.is_null() {
@@ -3,50 +3,156 @@
 //! This module provides access to CafeOS threads.
 
 pub mod builder;
+mod scoped;
 pub mod thread;
 pub mod ticks;
 
+use crate::time::Instant;
+use alloc::sync::Arc;
 pub use builder::Builder;
-use core::time::Duration;
+pub use scoped::{Scope, ScopedJoinHandle, scope};
+use core::{cell::UnsafeCell, fmt, mem::ManuallyDrop, time::Duration};
 use thiserror::Error;
-pub use thread::{Thread, ThreadError};
+pub use thread::{CoreMask, MAX_PRIORITY, Thread, ThreadError};
 use wut_sys as sys;
 
+/// Interval in which [JoinHandle::join_timeout] checks if the thread has finished.
+const JOIN_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
 #[derive(Debug, Error)]
 pub enum JoinError {
     #[error("This thread was detached")]
     Detached,
+    #[error("The thread exited without returning a value")]
+    NoResult,
+}
+
+/// Error returned by [JoinHandle::join_timeout].
+pub enum JoinTimeout<T> {
+    /// The thread did not finish in time. Contains the handle to retry joining or to detach the thread by dropping it.
+    TimedOut(JoinHandle<T>),
+    /// The thread finished, but joining it failed.
+    Failed(JoinError),
+}
+
+impl<T> fmt::Debug for JoinTimeout<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TimedOut(_) => f.write_str("TimedOut(..)"),
+            Self::Failed(e) => f.debug_tuple("Failed").field(e).finish(),
+        }
+    }
+}
+
+impl<T> fmt::Display for JoinTimeout<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TimedOut(_) => f.write_str("The thread did not finish in time"),
+            Self::Failed(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl<T> core::error::Error for JoinTimeout<T> {}
+
+/// Storage for the return value of a spawned thread.
+pub(crate) struct Packet<T>(UnsafeCell<Option<T>>);
+
+unsafe impl<T: Send> Sync for Packet<T> {}
+
+impl<T> Packet<T> {
+    pub(crate) fn new() -> Self {
+        Self(UnsafeCell::new(None))
+    }
+
+    /// Only called once by the spawned thread before it exits.
+    pub(crate) unsafe fn set(&self, value: T) {
+        unsafe { *self.0.get() = Some(value) };
+    }
+
+    /// Only called once after the spawned thread was joined.
+    unsafe fn take(&self) -> Option<T> {
+        unsafe { (*self.0.get()).take() }
+    }
 }
 
-#[derive(Clone)]
-pub struct JoinHandle {
+pub struct JoinHandle<T = ()> {
     thread: Thread,
+    packet: Arc<Packet<T>>,
 }
 
-unsafe impl Send for JoinHandle {}
-unsafe impl Sync for JoinHandle {}
+unsafe impl<T> Send for JoinHandle<T> {}
+unsafe impl<T> Sync for JoinHandle<T> {}
 
-impl JoinHandle {
-    pub fn new(thread: Thread) -> Self {
-        Self { thread: thread }
+impl<T> JoinHandle<T> {
+    pub(crate) fn new(thread: Thread, packet: Arc<Packet<T>>) -> Self {
+        Self { thread, packet }
     }
 
     pub fn thread(&self) -> &Thread {
         &self.thread
     }
 
-    pub fn join(self) -> Result<i32, JoinError> {
+    /// Set the scheduling priority of the thread. See [Thread::set_priority].
+    pub fn set_priority(&self, priority: u8) -> Result<(), ThreadError> {
+        self.thread.set_priority(priority)
+    }
+
+    /// Restrict the thread to the cores in `mask`. See [Thread::set_affinity].
+    pub fn set_affinity(&self, mask: CoreMask) -> Result<(), ThreadError> {
+        self.thread.set_affinity(mask)
+    }
+
+    /// Wait for the thread to finish and return its result.
+    pub fn join(self) -> Result<T, JoinError> {
+        // a joined thread must not be detached afterwards
+        let this = ManuallyDrop::new(self);
+        let packet = unsafe { core::ptr::read(&this.packet) };
+
         let mut result = 0;
-        let detached = unsafe { sys::OSJoinThread(self.thread.raw(), &mut result) };
+        let detached = unsafe { sys::OSJoinThread(this.thread.raw(), &mut result) };
 
         match detached {
             0 => Err(JoinError::Detached),
-            _ => Ok(result),
+            _ => unsafe { packet.take() }.ok_or(JoinError::NoResult),
         }
     }
+
+    /// Wait up to `timeout` for the thread to finish and return its result.
+    ///
+    /// On timeout the handle is returned, so the caller can try again or give up on the thread.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wut::thread::{self, JoinTimeout};
+    /// use core::time::Duration;
+    ///
+    /// let handle = thread::spawn(|| {
+    ///     thread::sleep(Duration::from_millis(200));
+    ///     42
+    /// })?;
+    ///
+    /// let Err(JoinTimeout::TimedOut(handle)) = handle.join_timeout(Duration::from_millis(10)) else {
+    ///     panic!("thread finished too early");
+    /// };
+    /// assert_eq!(handle.join_timeout(Duration::from_secs(1)).ok(), Some(42));
+    /// ```
+    pub fn join_timeout(self, timeout: Duration) -> Result<T, JoinTimeout<T>> {
+        let start = Instant::now();
+
+        while unsafe { sys::OSIsThreadTerminated(self.thread.raw()) } == 0 {
+            if start.elapsed() >= timeout {
+                return Err(JoinTimeout::TimedOut(self));
+            }
+            sleep(JOIN_POLL_INTERVAL.min(timeout.saturating_sub(start.elapsed())));
+        }
+
+        self.join().map_err(JoinTimeout::Failed)
+    }
 }
 
-impl Drop for JoinHandle {
+impl<T> Drop for JoinHandle<T> {
     fn drop(&mut self) {
         unsafe {
             sys::OSDetachThread(self.thread.raw());
@@ -76,6 +182,16 @@ impl Into<u32> for CpuCore {
     }
 }
 
+impl From<CpuCore> for CoreMask {
+    fn from(value: CpuCore) -> Self {
+        match value {
+            CpuCore::Core0 => CoreMask::CORE0,
+            CpuCore::Core1 => CoreMask::CORE1,
+            CpuCore::Core2 => CoreMask::CORE2,
+        }
+    }
+}
+
 pub fn default_thread(core: CpuCore) -> Thread {
     Thread::from(unsafe { sys::OSGetDefaultThread(core.into()) })
 }
@@ -95,9 +211,10 @@ pub fn terminate(exit_code: i32) {
 
 //
 
-pub fn spawn<F>(f: F) -> Result<JoinHandle, ThreadError>
+pub fn spawn<F, T>(f: F) -> Result<JoinHandle<T>, ThreadError>
 where
-    F: FnOnce() + Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
 {
     Builder::default().spawn(f)
 }
@@ -0,0 +1,129 @@
+// scoped
+
+use crate::{
+    sync::ManualEvent,
+    thread::{Builder, JoinError, JoinHandle, Thread, ThreadError},
+};
+use alloc::sync::Arc;
+use core::{
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// A scope to spawn scoped threads in. See [scope].
+pub struct Scope<'scope, 'env: 'scope> {
+    data: Arc<ScopeData>,
+    scope: PhantomData<&'scope mut &'scope ()>,
+    env: PhantomData<&'env mut &'env ()>,
+}
+
+/// Bookkeeping shared between a [Scope] and its threads.
+///
+/// Lives in an [Arc], because a thread may still touch it after the scope has returned.
+pub(crate) struct ScopeData {
+    running: AtomicUsize,
+    done: ManualEvent,
+}
+
+impl ScopeData {
+    pub(crate) fn increment(&self) {
+        self.running.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn decrement(&self) {
+        if self.running.fetch_sub(1, Ordering::Release) == 1 {
+            self.done.signal();
+        }
+    }
+
+    fn wait(&self) {
+        loop {
+            self.done.reset();
+            if self.running.load(Ordering::Acquire) == 0 {
+                break;
+            }
+            self.done.wait();
+        }
+    }
+}
+
+/// An owned permission to join on a scoped thread.
+pub struct ScopedJoinHandle<'scope, T> {
+    handle: JoinHandle<T>,
+    _scope: PhantomData<&'scope ()>,
+}
+
+impl<'scope, T> ScopedJoinHandle<'scope, T> {
+    pub(crate) fn new(handle: JoinHandle<T>) -> Self {
+        Self {
+            handle,
+            _scope: PhantomData,
+        }
+    }
+
+    pub fn thread(&self) -> &Thread {
+        self.handle.thread()
+    }
+
+    /// Wait for the thread to finish and return its result.
+    pub fn join(self) -> Result<T, JoinError> {
+        self.handle.join()
+    }
+}
+
+impl<'scope, 'env> Scope<'scope, 'env> {
+    /// Spawn a new thread within the scope.
+    ///
+    /// Unlike [spawn][super::spawn], the closure may borrow non-`'static` data from outside the scope. The thread is joined automatically when the scope ends.
+    pub fn spawn<F, T>(&'scope self, f: F) -> Result<ScopedJoinHandle<'scope, T>, ThreadError>
+    where
+        F: FnOnce() -> T + Send + 'scope,
+        T: Send + 'scope,
+    {
+        Builder::default().spawn_scoped(self, f)
+    }
+
+    pub(crate) fn data(&self) -> &Arc<ScopeData> {
+        &self.data
+    }
+}
+
+/// Create a scope for spawning scoped threads.
+///
+/// All threads spawned within the scope which haven't been joined manually are joined before this function returns, so they may borrow local data.
+///
+/// There is no unwinding on the Wii U, so a panic in any scoped thread aborts the whole application. Errors of manually joined threads are returned by [ScopedJoinHandle::join].
+///
+/// # Example
+///
+/// ```
+/// use wut::thread;
+///
+/// let mut data = [1, 2, 3, 4, 5, 6];
+/// let (left, right) = data.split_at_mut(3);
+///
+/// thread::scope(|s| {
+///     s.spawn(|| left.iter_mut().for_each(|x| *x *= 2)).unwrap();
+///     s.spawn(|| right.iter_mut().for_each(|x| *x *= 2)).unwrap();
+/// });
+///
+/// assert_eq!(data, [2, 4, 6, 8, 10, 12]);
+/// ```
+pub fn scope<'env, F, T>(f: F) -> T
+where
+    F: for<'scope> FnOnce(&'scope Scope<'scope, 'env>) -> T,
+{
+    let scope = Scope {
+        data: Arc::new(ScopeData {
+            running: AtomicUsize::new(0),
+            done: ManualEvent::new(),
+        }),
+        scope: PhantomData,
+        env: PhantomData,
+    };
+
+    let result = f(&scope);
+    scope.data.wait();
+
+    result
+}
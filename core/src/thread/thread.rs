@@ -4,7 +4,10 @@ use alloc::{
     ffi::NulError,
     string::{String, ToString},
 };
-use core::ffi::CStr;
+use core::{
+    ffi::CStr,
+    ops::{BitOr, BitOrAssign},
+};
 use flagset::{FlagSet, flags};
 use thiserror::Error;
 use wut_sys as sys;
@@ -35,6 +38,46 @@ flags! {
     }
 }
 
+/// Lowest possible thread priority.
+pub const MAX_PRIORITY: u8 = 31;
+
+/// Set of CPU cores a thread is allowed to run on.
+///
+/// Masks can be combined with `|`, e.g. `CoreMask::CORE1 | CoreMask::CORE2`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CoreMask(u8);
+
+impl CoreMask {
+    pub const CORE0: Self = Self(sys::OS_THREAD_ATTRIB::OS_THREAD_ATTRIB_AFFINITY_CPU0 as u8);
+    pub const CORE1: Self = Self(sys::OS_THREAD_ATTRIB::OS_THREAD_ATTRIB_AFFINITY_CPU1 as u8);
+    pub const CORE2: Self = Self(sys::OS_THREAD_ATTRIB::OS_THREAD_ATTRIB_AFFINITY_CPU2 as u8);
+    pub const ANY: Self = Self(sys::OS_THREAD_ATTRIB::OS_THREAD_ATTRIB_AFFINITY_ANY as u8);
+
+    #[inline]
+    pub const fn bits(&self) -> u8 {
+        self.0
+    }
+
+    #[inline]
+    pub const fn contains(&self, other: CoreMask) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for CoreMask {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for CoreMask {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
 /// Thread
 ///
 /// Note: `Thread` is not the owner of any data but a stack pointer. Dropping just drops the pointer and no underlying data.
@@ -54,6 +97,10 @@ pub enum ThreadError {
     NullPointer,
     #[error("")]
     InternalZeroByte(#[from] NulError),
+    #[error("CPU core {0} does not exist, valid cores are 0-2")]
+    InvalidCore(u8),
+    #[error("Failed to change the thread's scheduling parameters")]
+    SchedulingFailed,
 }
 
 impl From<*mut sys::OSThread> for Thread {
@@ -86,6 +133,27 @@ impl Thread {
         unsafe { (*self.0).basePriority }
     }
 
+    /// Set the scheduling priority of the thread.
+    ///
+    /// `0` is highest priority, `31` is lowest priority. Values above `31` are clamped.
+    pub fn set_priority(&self, priority: u8) -> Result<(), ThreadError> {
+        let priority = priority.min(MAX_PRIORITY) as i32;
+        match unsafe { sys::OSSetThreadPriority(self.0, priority) } {
+            0 => Err(ThreadError::SchedulingFailed),
+            _ => Ok(()),
+        }
+    }
+
+    /// Restrict the thread to run on the cores in `mask`.
+    ///
+    /// An empty mask is rejected by the OS.
+    pub fn set_affinity(&self, mask: CoreMask) -> Result<(), ThreadError> {
+        match unsafe { sys::OSSetThreadAffinity(self.0, mask.bits() as u32) } {
+            0 => Err(ThreadError::SchedulingFailed),
+            _ => Ok(()),
+        }
+    }
+
     pub fn park(&self) {
         unsafe { sys::OSSuspendThread(self.0) };
     }
@@ -1,7 +1,11 @@
 // datetime
 
 use crate::time::SystemTime;
-use core::{cmp::Ordering, fmt};
+use alloc::string::String;
+use core::{
+    cmp::Ordering,
+    fmt::{self, Write},
+};
 use wut_sys as sys;
 
 /// WiiU local system datetime.
@@ -31,10 +35,158 @@ pub struct DateTime {
     pub microsec: i32,
 }
 
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
 impl DateTime {
     pub fn now() -> Self {
         Self::from(SystemTime::now())
     }
+
+    /// Create a datetime from seconds since the Unix epoch (`1970-01-01T00:00:00`).
+    ///
+    /// Like the rest of [DateTime], the result is not adjusted for any timezone.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wut::time::DateTime;
+    ///
+    /// assert_eq!(DateTime::from_unix(0).to_string(), "1970-01-01T00:00:00");
+    /// assert_eq!(DateTime::from_unix(-1).to_string(), "1969-12-31T23:59:59");
+    /// assert_eq!(DateTime::from_unix(946_684_800).to_string(), "2000-01-01T00:00:00");
+    /// assert_eq!(DateTime::from_unix(1_700_000_000).to_string(), "2023-11-14T22:13:20");
+    /// ```
+    pub fn from_unix(secs: i64) -> Self {
+        let days = secs.div_euclid(SECONDS_PER_DAY);
+        let time = secs.rem_euclid(SECONDS_PER_DAY) as i32;
+        let (year, month, day) = civil_from_days(days);
+
+        Self {
+            sec: time % 60,
+            min: time / 60 % 60,
+            hour: time / 3600,
+            day: day as i32,
+            month: month as i32 - 1,
+            year: year as i32,
+            // 1970-01-01 was a Thursday
+            weekday: (days + 4).rem_euclid(7) as i32,
+            yearday: (days - days_from_civil(year, 1, 1)) as i32,
+            millisec: 0,
+            microsec: 0,
+        }
+    }
+
+    /// Seconds since the Unix epoch (`1970-01-01T00:00:00`). Sub-second fields are truncated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wut::time::DateTime;
+    ///
+    /// assert_eq!(DateTime::default().to_unix(), -62_135_596_800);
+    /// assert_eq!(DateTime::from_unix(1_700_000_000).to_unix(), 1_700_000_000);
+    /// ```
+    pub fn to_unix(&self) -> i64 {
+        let days = days_from_civil(self.year as i64, self.month as i64 + 1, self.day as i64);
+        days * SECONDS_PER_DAY + self.hour as i64 * 3600 + self.min as i64 * 60 + self.sec as i64
+    }
+
+    /// Format the datetime with `strftime`-style specifiers.
+    ///
+    /// | Specifier | Meaning                          | Example |
+    /// |-----------|----------------------------------|---------|
+    /// | `%Y`      | Year                             | `2024`  |
+    /// | `%m`      | Month (01-12)                    | `07`    |
+    /// | `%d`      | Day of the month (01-31)         | `04`    |
+    /// | `%H`      | Hour (00-23)                     | `13`    |
+    /// | `%M`      | Minute (00-59)                   | `05`    |
+    /// | `%S`      | Second (00-59)                   | `09`    |
+    /// | `%j`      | Day of the year (001-366)        | `186`   |
+    /// | `%a`      | Abbreviated weekday name         | `Thu`   |
+    /// | `%b`      | Abbreviated month name           | `Jul`   |
+    /// | `%%`      | A literal `%`                    | `%`     |
+    ///
+    /// Unknown specifiers are copied into the output unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wut::time::DateTime;
+    ///
+    /// let name = DateTime::now().format("screenshot_%Y%m%d_%H%M%S.bmp");
+    /// ```
+    pub fn format(&self, fmt: &str) -> String {
+        let mut out = String::with_capacity(fmt.len() + 16);
+        let mut chars = fmt.chars();
+
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+
+            // writing into a String cannot fail
+            let _ = match chars.next() {
+                Some('Y') => write!(out, "{:04}", self.year),
+                Some('m') => write!(out, "{:02}", self.month + 1),
+                Some('d') => write!(out, "{:02}", self.day),
+                Some('H') => write!(out, "{:02}", self.hour),
+                Some('M') => write!(out, "{:02}", self.min),
+                Some('S') => write!(out, "{:02}", self.sec),
+                Some('j') => write!(out, "{:03}", self.yearday + 1),
+                Some('a') => out.write_str(name(&WEEKDAYS, self.weekday)),
+                Some('b') => out.write_str(name(&MONTHS, self.month)),
+                Some('%') => out.write_char('%'),
+                Some(other) => write!(out, "%{other}"),
+                None => out.write_char('%'),
+            };
+        }
+
+        out
+    }
+}
+
+const SECONDS_PER_DAY: i64 = 86400;
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar. `month` is in the range 1-12.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    // years start in March, so the leap day is the last day of the year
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Inverse of [days_from_civil]. Returns year, month (1-12) and day (1-31).
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn name<'a>(names: &[&'a str], index: i32) -> &'a str {
+    usize::try_from(index)
+        .ok()
+        .and_then(|i| names.get(i))
+        .copied()
+        .unwrap_or("???")
 }
 
 impl Default for DateTime {
@@ -111,7 +263,7 @@ impl fmt::Display for DateTime {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{:04}/{:02}/{:02} {:02}:{:02}:{:02}",
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
             self.year,
             self.month + 1,
             self.day,
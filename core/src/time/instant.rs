@@ -0,0 +1,126 @@
+// instant
+
+use crate::thread::ticks::{nanos_to_ticks, ticks_to_nanos};
+use core::{ops, time::Duration};
+use wut_sys as sys;
+
+/// A measurement of a monotonically nondecreasing clock, useful for measuring frame times and other intervals.
+///
+/// Backed by the system tick counter (ticks since boot), so it is unaffected by changes to the system clock and unrelated to [DateTime][super::DateTime].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Instant(sys::OSTime);
+
+impl Instant {
+    pub fn now() -> Self {
+        Self(unsafe { sys::OSGetSystemTime() })
+    }
+
+    /// Amount of time elapsed since this instant was created.
+    pub fn elapsed(&self) -> Duration {
+        Instant::now().duration_since(*self)
+    }
+
+    /// Amount of time elapsed from `earlier` to `self`.
+    ///
+    /// Returns zero if `earlier` is later than `self`. Differences are computed with wrapping arithmetic, so the result stays correct across a wrap of the tick counter.
+    pub fn duration_since(&self, earlier: Instant) -> Duration {
+        let delta = self.0.wrapping_sub(earlier.0);
+        if delta > 0 {
+            Duration::from_nanos(ticks_to_nanos(delta as u64))
+        } else {
+            Duration::ZERO
+        }
+    }
+}
+
+impl ops::Add<Duration> for Instant {
+    type Output = Instant;
+    fn add(self, rhs: Duration) -> Self::Output {
+        Instant(self.0.wrapping_add(nanos_to_ticks(rhs.as_nanos() as u64) as i64))
+    }
+}
+
+impl ops::AddAssign<Duration> for Instant {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl ops::Sub<Duration> for Instant {
+    type Output = Instant;
+    fn sub(self, rhs: Duration) -> Self::Output {
+        Instant(self.0.wrapping_sub(nanos_to_ticks(rhs.as_nanos() as u64) as i64))
+    }
+}
+
+impl ops::SubAssign<Duration> for Instant {
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+impl ops::Sub<Instant> for Instant {
+    type Output = Duration;
+    fn sub(self, rhs: Instant) -> Self::Output {
+        self.duration_since(rhs)
+    }
+}
+
+/// Simple stopwatch for measuring total and per-lap time, e.g. the delta time of a game loop.
+///
+/// # Example
+///
+/// ```
+/// use wut::time::Stopwatch;
+///
+/// let mut stopwatch = Stopwatch::start();
+///
+/// while wut::process::running() {
+///     let delta = stopwatch.lap();
+///     // update game by `delta`
+/// }
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Stopwatch {
+    start: Instant,
+    lap: Instant,
+}
+
+impl Stopwatch {
+    /// Create a new stopwatch which starts running immediately.
+    pub fn start() -> Self {
+        let now = Instant::now();
+        Self {
+            start: now,
+            lap: now,
+        }
+    }
+
+    /// Time since the stopwatch was started or last reset.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Time since the last lap (or the start) and begin a new lap.
+    pub fn lap(&mut self) -> Duration {
+        let now = Instant::now();
+        let lap = now.duration_since(self.lap);
+        self.lap = now;
+        lap
+    }
+
+    /// Restart the stopwatch and return the total elapsed time.
+    pub fn reset(&mut self) -> Duration {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.start);
+        self.start = now;
+        self.lap = now;
+        elapsed
+    }
+}
+
+impl Default for Stopwatch {
+    fn default() -> Self {
+        Self::start()
+    }
+}
@@ -3,12 +3,60 @@
 pub use core::time::*;
 
 mod datetime;
+mod instant;
+mod timer;
 pub use datetime::DateTime;
+pub use instant::{Instant, Stopwatch};
+pub use timer::{Timeout, Timer};
 
-use crate::thread::ticks::{nanos_to_ticks, ticks_to_nanos};
-use core::ops;
+use crate::thread::{
+    self,
+    ticks::{nanos_to_ticks, ticks_to_nanos, timer_clock_speed},
+};
+use core::{hint, ops};
 use wut_sys as sys;
 
+/// Put the current thread to sleep for at least `duration`.
+///
+/// Same as [thread::sleep].
+#[inline]
+pub fn sleep(duration: Duration) {
+    thread::sleep(duration);
+}
+
+/// Put the current thread to sleep for at least `ms` milliseconds.
+#[inline]
+pub fn sleep_ms(ms: u32) {
+    sleep(Duration::from_millis(ms as u64));
+}
+
+/// Busy-wait for `duration` without yielding to the scheduler.
+///
+/// More precise than [sleep] for very short durations, but occupies the core for the whole time. Use sparingly.
+pub fn spin_sleep(duration: Duration) {
+    let start = Instant::now();
+    while start.elapsed() < duration {
+        hint::spin_loop();
+    }
+}
+
+/// Seconds between the Unix epoch (`1970-01-01T00:00:00`) and the epoch of the system clock (`2000-01-01T00:00:00`).
+const UNIX_EPOCH_OFFSET: i64 = 946_684_800;
+
+/// Current system time as seconds since the Unix epoch (`1970-01-01T00:00:00`).
+///
+/// The system clock stores local time, so this is only a real Unix timestamp if the console is set to UTC.
+///
+/// # Example
+///
+/// ```
+/// let now = wut::time::DateTime::from_unix(wut::time::now_unix());
+/// ```
+pub fn now_unix() -> i64 {
+    let ticks = unsafe { sys::OSGetTime() };
+    ticks.div_euclid(timer_clock_speed() as i64) + UNIX_EPOCH_OFFSET
+}
+
 /// A measurement of the system clock, useful for interacting with external entities such as the file system or other processes.
 ///
 /// Since the system clock can be arbitrarily set, `SystemTime` measurements do not have a real-time meaning. Earlier timestamps could have occurred at a later real time, and identical timestamps could have occurred at different real times. However, it is the base for time measurements available on the Wii U and can be considered the ground truth.
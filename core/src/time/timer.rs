@@ -0,0 +1,131 @@
+use crate::time::{Duration, Instant};
+
+/// A countdown which expires after a fixed duration, e.g. for respawn delays or hold detection.
+///
+/// Timers are passive and have to be checked with [is_expired][Timer::is_expired]. To run code once a timer expires, use [Timeout].
+///
+/// # Example
+///
+/// ```
+/// use core::time::Duration;
+/// use wut::time::Timer;
+///
+/// let mut spawn = Timer::new(Duration::from_secs(5));
+///
+/// while wut::process::running() {
+///     if spawn.is_expired() {
+///         // spawn an enemy every 5 seconds
+///         spawn.restart();
+///     }
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Timer {
+    deadline: Instant,
+    duration: Duration,
+}
+
+impl Timer {
+    /// Create a timer which starts counting down immediately.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            deadline: Instant::now() + duration,
+            duration,
+        }
+    }
+
+    /// Length of a countdown.
+    #[inline]
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Check if the countdown has run out.
+    pub fn is_expired(&self) -> bool {
+        self.remaining().is_zero()
+    }
+
+    /// Time left until the timer expires. Zero if it already has.
+    pub fn remaining(&self) -> Duration {
+        self.deadline.duration_since(Instant::now())
+    }
+
+    /// Start the countdown over from now.
+    pub fn reset(&mut self) {
+        self.deadline = Instant::now() + self.duration;
+    }
+
+    /// Start the next countdown at the moment the current one expires.
+    ///
+    /// Unlike [reset][Timer::reset], time spent between the expiry and this call is not lost, so repeating timers do not drift. If the timer is behind by more than a whole countdown, it expires again right away.
+    pub fn restart(&mut self) {
+        self.deadline += self.duration;
+    }
+}
+
+/// Calls a function once after a [Timer] expired.
+///
+/// The function runs on the thread calling [poll][Timeout::poll], usually once per frame in the main loop.
+///
+/// # Example
+///
+/// ```
+/// use core::time::Duration;
+/// use wut::time::Timeout;
+///
+/// let mut hide_toast = Timeout::new(Duration::from_secs(3), || println!("toast hidden"));
+///
+/// while wut::process::running() {
+///     hide_toast.poll();
+/// }
+/// ```
+pub struct Timeout<F: FnOnce()> {
+    timer: Timer,
+    f: Option<F>,
+}
+
+impl<F: FnOnce()> Timeout<F> {
+    pub fn new(duration: Duration, f: F) -> Self {
+        Self {
+            timer: Timer::new(duration),
+            f: Some(f),
+        }
+    }
+
+    /// Call the function if the timer has expired and it was not called yet.
+    ///
+    /// Returns `true` if the function was called by this call.
+    pub fn poll(&mut self) -> bool {
+        if !self.timer.is_expired() {
+            return false;
+        }
+
+        match self.f.take() {
+            Some(f) => {
+                f();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check if the function still has to be called.
+    #[inline]
+    pub fn is_pending(&self) -> bool {
+        self.f.is_some()
+    }
+
+    /// Time left until the function is called. Zero if it is due or was already called.
+    pub fn remaining(&self) -> Duration {
+        if self.is_pending() {
+            self.timer.remaining()
+        } else {
+            Duration::ZERO
+        }
+    }
+
+    /// Drop the function without calling it.
+    pub fn cancel(&mut self) {
+        self.f = None;
+    }
+}
@@ -1,10 +1,239 @@
 //! Information about foreground application.
 
+use crate::{
+    path::PathBuf,
+    rrc::{Rrc, RrcGuard},
+    sync::OnceLock,
+};
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+use core::ffi::{self, CStr};
+use thiserror::Error;
 use wut_sys as sys;
 
+pub(crate) static SAVE: Rrc = Rrc::new(
+    || unsafe {
+        sys::SAVEInit();
+    },
+    || unsafe {
+        sys::SAVEShutdown();
+    },
+);
+
+/// Keeps the save library initialized for the rest of the application.
+static SAVE_GUARD: OnceLock<RrcGuard> = OnceLock::new();
+
+#[derive(Debug, Error)]
+pub enum TitleError {
+    #[error("Title {0:#018x} is not installed")]
+    NotFound(u64),
+    #[error("Failed to open the MCP interface")]
+    McpUnavailable,
+    #[error("MCP request failed with error code {0:#x}")]
+    Mcp(i32),
+    #[error("Save request failed with error code {0}")]
+    Save(i32),
+}
+
+/// Kind of an installed title.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TitleType {
+    Game,
+    Update,
+    Dlc,
+    /// System applications, applets and other titles.
+    System(u32),
+}
+
+impl From<sys::MCPAppType::Type> for TitleType {
+    fn from(value: sys::MCPAppType::Type) -> Self {
+        use sys::MCPAppType as T;
+        match value {
+            T::MCP_APP_TYPE_GAME => Self::Game,
+            T::MCP_APP_TYPE_GAME_UPDATE => Self::Update,
+            T::MCP_APP_TYPE_GAME_DLC => Self::Dlc,
+            v => Self::System(v as u32),
+        }
+    }
+}
+
+/// Storage device a title is installed on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageDevice {
+    /// Internal storage (MLC).
+    Internal,
+    Usb,
+    Disc,
+    Unknown(String),
+}
+
+impl From<&str> for StorageDevice {
+    fn from(value: &str) -> Self {
+        match value {
+            "mlc" => Self::Internal,
+            "usb" => Self::Usb,
+            "odd" => Self::Disc,
+            v => Self::Unknown(String::from(v)),
+        }
+    }
+}
+
+/// An installed title. See [installed].
+#[derive(Debug, Clone)]
+pub struct TitleInfo {
+    pub id: u64,
+    /// English short name from the title's meta data. Empty if unavailable.
+    pub name: String,
+    pub kind: TitleType,
+    pub device: StorageDevice,
+}
+
 /// Get the title ID of the current foreground application.
 ///
 /// Lists of title ID are available online.
 pub fn current_title() -> u64 {
     unsafe { sys::OSGetTitleID() }
 }
+
+/// List all installed titles.
+///
+/// # Example
+///
+/// ```
+/// for title in wut::title::installed()? {
+///     println!("{:016x} {}", title.id, title.name);
+/// }
+/// ```
+pub fn installed() -> Result<Vec<TitleInfo>, TitleError> {
+    let handle = unsafe { sys::MCP_Open() };
+    if handle < 0 {
+        return Err(TitleError::McpUnavailable);
+    }
+
+    let count = unsafe { sys::MCP_TitleCount(handle) };
+    let mut list = vec![sys::MCPTitleListType::default(); count.max(0) as usize];
+    let mut written = 0;
+
+    let status = unsafe {
+        sys::MCP_TitleList(
+            handle,
+            &mut written,
+            list.as_mut_ptr(),
+            (list.len() * size_of::<sys::MCPTitleListType>()) as u32,
+        )
+    };
+    unsafe {
+        sys::MCP_Close(handle);
+    }
+
+    if status < 0 {
+        return Err(TitleError::Mcp(status));
+    }
+    list.truncate(written as usize);
+
+    unsafe {
+        sys::ACPInitialize();
+    }
+    let mut meta = Box::new(sys::ACPMetaXml::default());
+
+    let titles = list
+        .iter()
+        .map(|title| {
+            let name = if unsafe { sys::ACPGetTitleMetaXml(title.titleId, &mut *meta) } == 0 {
+                c_str(&meta.shortname_en)
+            } else {
+                String::new()
+            };
+
+            TitleInfo {
+                id: title.titleId,
+                name,
+                kind: TitleType::from(title.appType),
+                device: StorageDevice::from(c_str(&title.indexedDevice).as_str()),
+            }
+        })
+        .collect();
+
+    unsafe {
+        sys::ACPFinalize();
+    }
+
+    Ok(titles)
+}
+
+/// Check if the title with `id` is installed.
+pub fn exists(id: u64) -> bool {
+    unsafe { sys::SYSCheckTitleExists(id) != 0 }
+}
+
+/// Launch the title with `id`.
+///
+/// The launch happens once the current application leaves the foreground, so the caller should keep running its [process::running][crate::process::running] loop afterwards.
+pub fn launch(id: u64) -> Result<(), TitleError> {
+    if !exists(id) {
+        return Err(TitleError::NotFound(id));
+    }
+
+    unsafe {
+        sys::SYSLaunchTitle(id);
+    }
+    Ok(())
+}
+
+/// Return to the Wii U Menu.
+///
+/// Like [launch], the caller should keep running its [process::running][crate::process::running] loop afterwards.
+pub fn launch_menu() {
+    unsafe {
+        sys::SYSLaunchMenu();
+    }
+}
+
+/// Directory containing the save data of the current title.
+///
+/// It contains the `common` directory shared by all accounts and one directory per account, named after the account's persistent ID (e.g. `80000001`). The returned path can be used directly with [fs][crate::fs].
+///
+/// # Example
+///
+/// ```
+/// let path = wut::title::save_dir()?.join("common/progress.bin");
+/// let data = wut::fs::read(path)?;
+/// ```
+pub fn save_dir() -> Result<PathBuf, TitleError> {
+    init_save();
+    Ok(PathBuf::from("/vol/save"))
+}
+
+/// Directory containing the common save data of the title with `id`.
+///
+/// Accessing other titles' save data may require additional permissions.
+pub fn save_dir_for(id: u64) -> Result<PathBuf, TitleError> {
+    if id == current_title() {
+        return save_dir();
+    }
+
+    init_save();
+
+    let mut buffer = [0 as ffi::c_char; 0x280];
+    let status = unsafe {
+        sys::SAVEGetSharedSaveDataPath(id, c"".as_ptr(), buffer.as_mut_ptr(), buffer.len() as u32)
+    };
+
+    if status != 0 {
+        Err(TitleError::Save(status))
+    } else {
+        Ok(PathBuf::from(c_str(&buffer)))
+    }
+}
+
+/// Mount the save directory once. It stays mounted until the application exits.
+fn init_save() {
+    SAVE_GUARD.get_or_init(|| SAVE.acquire());
+}
+
+/// Convert a fixed size, null terminated C string buffer.
+fn c_str(buffer: &[ffi::c_char]) -> String {
+    let bytes = unsafe { core::slice::from_raw_parts(buffer.as_ptr() as *const u8, buffer.len()) };
+    CStr::from_bytes_until_nul(bytes)
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
@@ -4,44 +4,51 @@
 // Only keep private stuff here
 // Having public stuff crammed into "utils" in not good design
 
-use alloc::{string::String, vec::Vec};
+use alloc::string::String;
 
 pub(crate) fn text_wrap<T: AsRef<str>>(text: T, width: usize) -> String {
     let text = text.as_ref();
+    let width = width.max(1);
     let mut result = String::new();
-    let mut line = String::new();
 
-    for word in text.split_whitespace() {
-        if (line.len() + word.len() + 1) <= width {
-            line.push_str(word);
-            line.push(' ');
-        } else {
-            result.push_str(line.trim_end());
+    for (i, paragraph) in text.split('\n').enumerate() {
+        if i > 0 {
             result.push('\n');
-            line = String::from(word);
-            line.push(' ');
         }
-    }
 
-    if !line.is_empty() {
-        result.push_str(line.trim_end());
+        let mut line = String::new();
+        let mut line_len = 0;
+
+        for word in paragraph.split_whitespace() {
+            let mut word = word;
+            let mut word_len = word.chars().count();
+
+            if line_len > 0 && line_len + 1 + word_len > width {
+                result.push_str(&line);
+                result.push('\n');
+                line.clear();
+                line_len = 0;
+            }
+
+            // hard-split words which don't fit on a line of their own
+            while word_len > width {
+                let split = word.char_indices().nth(width).map_or(word.len(), |(i, _)| i);
+                result.push_str(&word[..split]);
+                result.push('\n');
+                word = &word[split..];
+                word_len -= width;
+            }
+
+            if line_len > 0 {
+                line.push(' ');
+                line_len += 1;
+            }
+            line.push_str(word);
+            line_len += word_len;
+        }
+
+        result.push_str(&line);
     }
 
     result
 }
-
-pub(crate) fn into_utf16(s: &str) -> Vec<u16> {
-    let mut v: Vec<u16> = s.encode_utf16().collect();
-    v.push(0);
-    v
-}
-
-pub(crate) fn from_utf16(ptr: *const u16) -> String {
-    let len = (0..)
-        .take_while(|&i| unsafe { *ptr.offset(i) } != 0)
-        .count()
-        .min(256);
-    let v = unsafe { core::slice::from_raw_parts(ptr, len) };
-
-    String::from_utf16_lossy(v)
-}
@@ -6,6 +6,8 @@ use syn::{parse_macro_input, punctuated::Punctuated, ItemFn, Meta, Token};
 /// 
 /// Optionally, a list of attribute values of available logger channels can be used to set up logging.
 /// 
+/// With `core = N` (`0`, `1` or `2`), the body of `main` runs in a thread pinned to that CPU core with a stack of 1 MiB. Initialization and deinitialization still happen on the default core.
+/// 
 /// As the Wii U needs custom code to run before, in, and after main this macro hides this implementation from the user.
 /// 
 /// # Example
@@ -19,6 +21,13 @@ use syn::{parse_macro_input, punctuated::Punctuated, ItemFn, Meta, Token};
 /// #[wut::main(Udp)]
 /// fn main() { }
 /// ```
+/// 
+/// Running `main` on core 2:
+/// 
+/// ```rust
+/// #[wut::main(Udp, core = 2)]
+/// fn main() { }
+/// ```
 #[proc_macro_attribute]
 pub fn main(attr: TokenStream, input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as ItemFn);
@@ -31,32 +40,144 @@ pub fn main(attr: TokenStream, input: TokenStream) -> TokenStream {
 
     // Parse the attributes and generate the corresponding code
     let mut custom_args = Vec::new();
+    let mut core = None;
     for arg in args {
-        if let Meta::Path(path) = arg {
-            if let Some(ident) = path.get_ident() {
-                custom_args.push(quote! { ::wut::logger::#ident });
+        match arg {
+            Meta::Path(path) => {
+                if let Some(ident) = path.get_ident() {
+                    custom_args.push(quote! { ::wut::logger::#ident });
+                }
             }
+            Meta::NameValue(meta) if meta.path.is_ident("core") => {
+                let value = match &meta.value {
+                    syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(lit), .. }) => {
+                        lit.base10_parse::<u8>().ok()
+                    }
+                    _ => None,
+                };
+                match value {
+                    Some(n @ 0..=2) => core = Some(n),
+                    _ => {
+                        return syn::Error::new_spanned(&meta.value, "`core` must be 0, 1 or 2")
+                            .to_compile_error()
+                            .into();
+                    }
+                }
+            }
+            _ => (),
         }
     }
 
+    // run the body in a thread pinned to the requested core and wait for it to finish
+    let body = match core {
+        Some(core) => quote! {
+            ::wut::thread::Builder::default()
+                .name("main")
+                .core(#core)
+                .stack_size(1024 * 1024usize)
+                .spawn(move || #block)
+                .expect("failed to spawn main thread")
+                .join()
+                .expect("failed to join main thread");
+        },
+        None => quote! { #block },
+    };
+
     let expanded = quote! {
         #[unsafe(no_mangle)]
-        pub extern "C" fn #func_name() {
-            ::wut::process::init(#(#custom_args)|*);
-            #block
+        pub extern "C" fn #func_name(
+            argc: ::core::ffi::c_int,
+            argv: *const *const ::core::ffi::c_char,
+        ) -> ::core::ffi::c_int {
+            unsafe { ::wut::process::init_with_args(#(#custom_args)|*, argc, argv) };
+            #body
             ::wut::process::deinit();
+            0
         }
     };
 
     TokenStream::from(expanded)
 }
 
+/// Marks a function as an on-console test.
+/// 
+/// The function is turned into a `wut::test::Test` constant of the same name and registered before `main` runs, so `wut::test_main!()` finds it automatically. It must not take any arguments and return either `()` or a `Result<(), E>` where `E: Debug`.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// #[wut::test]
+/// fn addition() {
+///     assert_eq!(1 + 1, 2);
+/// }
+/// 
+/// #[wut::main(Console)]
+/// fn main() {
+///     wut::test_main!();
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn test(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ItemFn);
+
+    if !attr.is_empty() {
+        return syn::Error::new_spanned(
+            proc_macro2::TokenStream::from(attr),
+            "`#[wut::test]` does not take any arguments",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    if !input.sig.inputs.is_empty() {
+        return syn::Error::new_spanned(&input.sig.inputs, "test functions cannot take arguments")
+            .to_compile_error()
+            .into();
+    }
+
+    let attrs = &input.attrs;
+    let vis = &input.vis;
+    let name = &input.sig.ident;
+    let output = &input.sig.output;
+    let block = &input.block;
+
+    let expanded = quote! {
+        #[allow(non_upper_case_globals)]
+        #vis const #name: ::wut::test::Test = ::wut::test::Test {
+            name: concat!(module_path!(), "::", stringify!(#name)),
+            run: {
+                #(#attrs)*
+                fn #name() #output #block
+
+                || ::wut::test::TestResult::into_result(#name())
+            },
+        };
+
+        // registered by a constructor, which runs before `main`
+        const _: () = {
+            #[used]
+            #[unsafe(link_section = ".init_array")]
+            static REGISTER: extern "C" fn() = {
+                extern "C" fn register() {
+                    static REGISTRATION: ::wut::test::Registration =
+                        ::wut::test::Registration::new(#name);
+                    REGISTRATION.register();
+                }
+                register
+            };
+        };
+    };
+
+    TokenStream::from(expanded)
+}
+
 /// Automatically implements the gx2 `Attributes` trait for a struct.
 /// 
 /// The struct should only contain named fields of type `wut::gx2::shader::Attribute` and can optionally contain the following (Rust) attributes:
 /// * #\[name = `&str`\]: sets the name of the attribute in the shader. Defaults to field name.
 /// * #\[index = `u32`\]: sets the internal attribute index. Defaults to 0 for the first field and increaes with *every* field from the last value.
 /// * #\[offset = `u32`\]: sets the internal attribute offset. Defaults to 0.
+/// * #\[format = `&str`\]: sets the format in which the shader reads the attribute, named like the attribute format types (e.g. `"Norm4"`, `"Snorm2"`, `"Uint4"`). Defaults to the format of the field's type. The format must have the same size as the field's format.
 /// 
 /// # Example
 /// 
@@ -71,9 +192,13 @@ pub fn main(attr: TokenStream, input: TokenStream) -> TokenStream {
 ///     // #[index = 1]
 ///     // #[offset = 0]
 ///     a_color: wut::gx2::shader::Attribute<Float4>,
+///     // 4 bytes per vertex instead of 16, read as `vec4` in 0.0..=1.0
+///     #[name = "aTint"]
+///     #[format = "Norm4"]
+///     a_tint: wut::gx2::shader::Attribute<Uint4>,
 /// }
 /// ```
-#[proc_macro_derive(ShaderAttributes, attributes(name, index, offset))]
+#[proc_macro_derive(ShaderAttributes, attributes(name, index, offset, format))]
 pub fn gx2_attributes(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as syn::DeriveInput);
 
@@ -101,6 +226,7 @@ pub fn gx2_attributes(input: TokenStream) -> TokenStream {
 
             let mut name = field_name.to_string();
             let mut offset = 0;
+            let mut format = None;
 
             for attr in &field.attrs {
                 if attr.path().is_ident("name") {
@@ -127,11 +253,56 @@ pub fn gx2_attributes(input: TokenStream) -> TokenStream {
                             }
                         }
                     }
+                } else if attr.path().is_ident("format") {
+                    if let Ok(meta) = attr.meta.require_name_value() {
+                        if let syn::Expr::Lit(expr_lit) = &meta.value {
+                            if let syn::Lit::Str(lit) = &expr_lit.lit {
+                                match attribute_format(&lit.value()) {
+                                    Some(f) => format = Some(f),
+                                    None => {
+                                        let message = format!(
+                                            "unknown attribute format \"{}\", expected one of: {}",
+                                            lit.value(),
+                                            ATTRIBUTE_FORMATS.iter().map(|(name, ..)| *name).collect::<Vec<_>>().join(", ")
+                                        );
+                                        let error = syn::Error::new_spanned(lit, message).to_compile_error();
+                                        return quote! { #field_name: { #error }, };
+                                    }
+                                }
+                            }
+                        }
+                    }
                 }
             }
 
-            let out = quote! {
-                #field_name: ::wut::gx2::shader::attribute::Attribute::new(group, #name, #index, #offset)?,
+            let out = match format {
+                Some((constant, size)) => {
+                    let constant = quote::format_ident!("{}", constant);
+                    // the size of the field's format can only be checked if its type is spelled out
+                    let check = attribute_inner_type(&field.ty).map(|ty| quote! {
+                        const {
+                            assert!(
+                                ::core::mem::size_of::<#ty>() == #size,
+                                "`format` must have the same size as the attribute type"
+                            )
+                        };
+                    });
+                    quote! {
+                        #field_name: {
+                            #check
+                            ::wut::gx2::shader::attribute::Attribute::with_format(
+                                group,
+                                #name,
+                                #index,
+                                #offset,
+                                ::wut::sys::GX2AttribFormat::#constant,
+                            )?
+                        },
+                    }
+                }
+                None => quote! {
+                    #field_name: ::wut::gx2::shader::attribute::Attribute::new(group, #name, #index, #offset)?,
+                },
             };
 
             index += 1;
@@ -155,6 +326,47 @@ pub fn gx2_attributes(input: TokenStream) -> TokenStream {
     output.into()
 }
 
+/// Names accepted by `#[format = "..."]` with their `GX2AttribFormat` and size in bytes.
+const ATTRIBUTE_FORMATS: [(&str, &str, usize); 16] = [
+    ("Float4", "GX2_ATTRIB_FORMAT_FLOAT_32_32_32_32", 16),
+    ("Float3", "GX2_ATTRIB_FORMAT_FLOAT_32_32_32", 12),
+    ("Float2", "GX2_ATTRIB_FORMAT_FLOAT_32_32", 8),
+    ("Float", "GX2_ATTRIB_FORMAT_FLOAT_32", 4),
+    ("Int4", "GX2_ATTRIB_FORMAT_SINT_8_8_8_8", 4),
+    ("Int2", "GX2_ATTRIB_FORMAT_SINT_8_8", 2),
+    ("Int", "GX2_ATTRIB_FORMAT_SINT_8", 1),
+    ("Uint4", "GX2_ATTRIB_FORMAT_UINT_8_8_8_8", 4),
+    ("Uint2", "GX2_ATTRIB_FORMAT_UINT_8_8", 2),
+    ("Uint", "GX2_ATTRIB_FORMAT_UINT_8", 1),
+    ("Snorm4", "GX2_ATTRIB_FORMAT_SNORM_8_8_8_8", 4),
+    ("Snorm2", "GX2_ATTRIB_FORMAT_SNORM_8_8", 2),
+    ("Snorm", "GX2_ATTRIB_FORMAT_SNORM_8", 1),
+    ("Norm4", "GX2_ATTRIB_FORMAT_UNORM_8_8_8_8", 4),
+    ("Norm2", "GX2_ATTRIB_FORMAT_UNORM_8_8", 2),
+    ("Norm", "GX2_ATTRIB_FORMAT_UNORM_8", 1),
+];
+
+fn attribute_format(name: &str) -> Option<(&'static str, usize)> {
+    ATTRIBUTE_FORMATS
+        .iter()
+        .find(|(n, ..)| *n == name)
+        .map(|(_, constant, size)| (*constant, *size))
+}
+
+/// Extract `T` from a field type like `Attribute<T>`.
+fn attribute_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let syn::PathArguments::AngleBracketed(args) = &path.path.segments.last()?.arguments else {
+        return None;
+    };
+    args.args.iter().find_map(|arg| match arg {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })
+}
+
 // #[proc_macro]
 // pub fn glsl(input: TokenStream) -> TokenStream {
 
@@ -70,6 +70,8 @@ fn main() {
         .raw_line("#![allow(non_snake_case)]")
         .raw_line("#![allow(dead_code)]")
         .raw_line(r#"unsafe extern "C" { pub fn ceil(arg1: f64) -> f64;}"#) // add ceil manually
+        // only declared with _GNU_SOURCE, but always part of newlib's libm
+        .raw_line(r#"unsafe extern "C" { pub fn sincos(x: f64, sin: *mut f64, cos: *mut f64); pub fn sincosf(x: f32, sin: *mut f32, cos: *mut f32);}"#)
         .generate()
         .expect("Unable to generate bindings");
 
@@ -1,6 +1,7 @@
 #![no_std]
 
 mod bindings;
+pub mod vec;
 
 use bindings as sys;
 use core::{f32, f64};
@@ -101,6 +102,13 @@ pub trait FloatingMathExt {
     /// Computes the sine of a number (in radians).
     fn sin(self) -> Self;
 
+    /// Simultaneously computes the sine and cosine of a number (in radians). Returns `(sin(x), cos(x))`.
+    ///
+    /// Faster than calling [sin][FloatingMathExt::sin] and [cos][FloatingMathExt::cos] separately.
+    fn sin_cos(self) -> (Self, Self)
+    where
+        Self: Sized;
+
     /// Hyperbolic sine function.
     fn sinh(self) -> Self;
 
@@ -124,8 +132,99 @@ pub trait FloatingMathExt {
     /// Convert degree to radians.
     fn to_radians(self) -> Self;
 
-    /// Round down if `x < 0.5` else up.
+    /// Returns the nearest integer to `self`. Rounds half-way cases away from `0.0`.
+    ///
+    /// ```
+    /// use wut_math::FloatingMathExt;
+    ///
+    /// assert_eq!(FloatingMathExt::round(0.49f32), 0.0);
+    /// assert_eq!(FloatingMathExt::round(0.5f32), 1.0);
+    /// assert_eq!(FloatingMathExt::round(-0.5f32), -1.0);
+    /// assert_eq!(FloatingMathExt::round(2.5f32), 3.0);
+    /// ```
     fn round(self) -> Self;
+
+    /// Returns the integer part of `self`. This means that non-integer numbers are always truncated towards zero.
+    ///
+    /// ```
+    /// use wut_math::FloatingMathExt;
+    ///
+    /// assert_eq!(FloatingMathExt::trunc(-3.7f32), -3.0);
+    /// assert!(FloatingMathExt::trunc(-0.5f32).is_sign_negative());
+    /// ```
+    fn trunc(self) -> Self;
+
+    /// Returns the fractional part of `self`, computed as `self - self.trunc()`.
+    ///
+    /// ```
+    /// use wut_math::FloatingMathExt;
+    ///
+    /// assert_eq!(FloatingMathExt::fract(-3.5f32), -0.5);
+    /// ```
+    fn fract(self) -> Self;
+
+    /// Returns a number that represents the sign of `self`.
+    ///
+    /// * `1.0` if the number is positive, `+0.0` or `INFINITY`
+    /// * `-1.0` if the number is negative, `-0.0` or `NEG_INFINITY`
+    /// * `NaN` if the number is `NaN`
+    fn signum(self) -> Self;
+
+    /// Returns a number composed of the magnitude of `self` and the sign of `sign`.
+    ///
+    /// ```
+    /// use wut_math::FloatingMathExt;
+    ///
+    /// assert_eq!(FloatingMathExt::copysign(3.0f32, -0.0), -3.0);
+    /// assert_eq!(FloatingMathExt::copysign(-3.0f32, 0.0), 3.0);
+    /// ```
+    fn copysign(self, sign: Self) -> Self;
+
+    /// Returns the minimum of the two numbers.
+    ///
+    /// Follows the semantics of [f32::min]: if one of the arguments is NaN, the other argument is returned.
+    fn min(self, other: Self) -> Self;
+
+    /// Returns the maximum of the two numbers.
+    ///
+    /// Follows the semantics of [f32::max]: if one of the arguments is NaN, the other argument is returned.
+    fn max(self, other: Self) -> Self;
+
+    /// Restrict a value to the interval `[min, max]`.
+    ///
+    /// Returns `max` if `self` is greater than `max`, and `min` if `self` is less than `min`. Otherwise this returns `self`. NaN is returned unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min > max`, `min` is NaN, or `max` is NaN.
+    fn clamp(self, min: Self, max: Self) -> Self;
+
+    /// Linear interpolation between `self` and `to`, computed as `self + (to - self) * t`.
+    ///
+    /// `t` is not clamped, so values outside of `[0, 1]` extrapolate.
+    fn lerp(self, to: Self, t: Self) -> Self;
+
+    /// Break `self` into a normalized fraction and an integral power of two, such that `self = fraction * 2^exp`.
+    ///
+    /// The fraction lies in `[0.5, 1)` (or is zero).
+    ///
+    /// ```
+    /// use wut_math::FloatingMathExt;
+    ///
+    /// for x in [1.0f32, -3.75, 0.1, 1024.0] {
+    ///     let (fraction, exp) = x.frexp();
+    ///     assert_eq!(fraction.ldexp(exp), x);
+    /// }
+    /// ```
+    fn frexp(self) -> (Self, i32)
+    where
+        Self: Sized;
+
+    /// Multiply `self` by `2^exp`.
+    fn ldexp(self, exp: i32) -> Self;
+
+    /// Returns the next representable value after `self` in the direction of `toward`.
+    fn nextafter(self, toward: Self) -> Self;
 }
 
 impl FloatingMathExt for f32 {
@@ -225,6 +324,12 @@ impl FloatingMathExt for f32 {
         unsafe { sys::sinf(self) }
     }
 
+    fn sin_cos(self) -> (Self, Self) {
+        let (mut sin, mut cos) = (0.0, 0.0);
+        unsafe { sys::sincosf(self, &mut sin, &mut cos) };
+        (sin, cos)
+    }
+
     fn sinh(self) -> Self {
         unsafe { sys::sinhf(self) }
     }
@@ -254,7 +359,73 @@ impl FloatingMathExt for f32 {
     }
 
     fn round(self) -> Self {
-        (self + 0.51).floor()
+        unsafe { sys::roundf(self) }
+    }
+
+    fn trunc(self) -> Self {
+        unsafe { sys::truncf(self) }
+    }
+
+    fn fract(self) -> Self {
+        self - FloatingMathExt::trunc(self)
+    }
+
+    fn signum(self) -> Self {
+        if self.is_nan() {
+            f32::NAN
+        } else {
+            unsafe { sys::copysignf(1.0, self) }
+        }
+    }
+
+    fn copysign(self, sign: Self) -> Self {
+        unsafe { sys::copysignf(self, sign) }
+    }
+
+    fn min(self, other: Self) -> Self {
+        if self.is_nan() || other < self {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn max(self, other: Self) -> Self {
+        if self.is_nan() || other > self {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn clamp(self, min: Self, max: Self) -> Self {
+        assert!(min <= max, "min > max, or either was NaN. min = {min:?}, max = {max:?}");
+
+        if self < min {
+            min
+        } else if self > max {
+            max
+        } else {
+            self
+        }
+    }
+
+    fn lerp(self, to: Self, t: Self) -> Self {
+        self + (to - self) * t
+    }
+
+    fn frexp(self) -> (Self, i32) {
+        let mut exp = 0;
+        let fraction = unsafe { sys::frexpf(self, &mut exp) };
+        (fraction, exp)
+    }
+
+    fn ldexp(self, exp: i32) -> Self {
+        unsafe { sys::ldexpf(self, exp) }
+    }
+
+    fn nextafter(self, toward: Self) -> Self {
+        unsafe { sys::nextafterf(self, toward) }
     }
 }
 
@@ -355,6 +526,12 @@ impl FloatingMathExt for f64 {
         unsafe { sys::sin(self) }
     }
 
+    fn sin_cos(self) -> (Self, Self) {
+        let (mut sin, mut cos) = (0.0, 0.0);
+        unsafe { sys::sincos(self, &mut sin, &mut cos) };
+        (sin, cos)
+    }
+
     fn sinh(self) -> Self {
         unsafe { sys::sinh(self) }
     }
@@ -384,6 +561,72 @@ impl FloatingMathExt for f64 {
     }
 
     fn round(self) -> Self {
-        (self + 0.51).floor()
+        unsafe { sys::round(self) }
+    }
+
+    fn trunc(self) -> Self {
+        unsafe { sys::trunc(self) }
+    }
+
+    fn fract(self) -> Self {
+        self - FloatingMathExt::trunc(self)
+    }
+
+    fn signum(self) -> Self {
+        if self.is_nan() {
+            f64::NAN
+        } else {
+            unsafe { sys::copysign(1.0, self) }
+        }
+    }
+
+    fn copysign(self, sign: Self) -> Self {
+        unsafe { sys::copysign(self, sign) }
+    }
+
+    fn min(self, other: Self) -> Self {
+        if self.is_nan() || other < self {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn max(self, other: Self) -> Self {
+        if self.is_nan() || other > self {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn clamp(self, min: Self, max: Self) -> Self {
+        assert!(min <= max, "min > max, or either was NaN. min = {min:?}, max = {max:?}");
+
+        if self < min {
+            min
+        } else if self > max {
+            max
+        } else {
+            self
+        }
+    }
+
+    fn lerp(self, to: Self, t: Self) -> Self {
+        self + (to - self) * t
+    }
+
+    fn frexp(self) -> (Self, i32) {
+        let mut exp = 0;
+        let fraction = unsafe { sys::frexp(self, &mut exp) };
+        (fraction, exp)
+    }
+
+    fn ldexp(self, exp: i32) -> Self {
+        unsafe { sys::ldexp(self, exp) }
+    }
+
+    fn nextafter(self, toward: Self) -> Self {
+        unsafe { sys::nextafter(self, toward) }
     }
 }
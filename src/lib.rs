@@ -16,18 +16,21 @@ extern crate alloc as alloc_crate;
 
 pub use wut_core::*;
 pub use wut_macros as macros;
+pub use wut_macros::test;
 pub use wut_math::FloatingMathExt;
+pub use wut_math as math;
 pub use wut_sys as sys;
 
-pub use alloc_crate::{borrow, boxed, collections, fmt, format, rc, slice, str, string, sync, vec};
+pub use alloc_crate::{borrow, boxed, fmt, format, rc, slice, str, string, vec};
 pub use core::{
-    any, arch, array, ascii, cell, char, clone, cmp, convert, default, error, f32, f64, hash, hint,
-    iter, marker, mem, net, num, ops, option, panic, pin, primitive, result,
+    any, arch, array, ascii, cell, char, clone, cmp, convert, default, error, f32, f64, hint, iter,
+    marker, mem, num, ops, option, panic, pin, primitive, result,
 };
 
 pub mod ffi {
     pub use alloc_crate::ffi::{CString, FromVecWithNulError, IntoStringError, NulError};
     pub use core::ffi::*;
+    pub use wut_core::ffi::{from_utf16, into_utf16};
 }
 
 /// The `wut` prelude
@@ -5,10 +5,19 @@ extern crate alloc;
 mod bindings;
 pub use bindings::*;
 
+/// Set while a panic is handled, so a panic inside the handler skips straight to the reboot.
+#[cfg(feature = "panic_handler")]
+static PANICKING: core::sync::atomic::AtomicBool = core::sync::atomic::AtomicBool::new(false);
+
 #[cfg(feature = "panic_handler")]
 #[panic_handler]
 fn panic_handler(info: &core::panic::PanicInfo) -> ! {
-    use alloc::{alloc::GlobalAlloc, format};
+    use alloc::{alloc::GlobalAlloc, ffi::CString, format};
+    use core::sync::atomic::Ordering;
+
+    if PANICKING.swap(true, Ordering::SeqCst) {
+        reboot();
+    }
 
     let (file, line, column, msg) = if let Some(location) = info.location() {
         (
@@ -21,9 +30,17 @@ fn panic_handler(info: &core::panic::PanicInfo) -> ! {
         ("<unknown>", 0, 0, info.message())
     };
 
-    let file = ::alloc::ffi::CString::new(format!("File: {file} - {line}:{column}")).unwrap();
-    let msg =
-        ::alloc::ffi::CString::new(format!("Reason: {}", msg.as_str().unwrap_or(""))).unwrap();
+    // Log through the WHB logging channels first. They are only written to if they were
+    // initialized, e.g. via `logger::udp`. The logger's mutex is not touched, as it may be
+    // held or poisoned by the panicking thread.
+    if let Ok(log) = CString::new(format!("Panic at {file}:{line}:{column}: {msg}")) {
+        unsafe {
+            WHBLogPrint(log.as_ptr());
+        }
+    }
+
+    let file = CString::new(format!("File: {file} - {line}:{column}")).unwrap_or_default();
+    let msg = CString::new(format!("Reason: {}", msg.as_str().unwrap_or(""))).unwrap_or_default();
 
     unsafe {
         OSScreenInit();
@@ -69,7 +86,11 @@ fn panic_handler(info: &core::panic::PanicInfo) -> ! {
         GLOBAL_ALLOCATOR.dealloc(drc_buffer, drc_layout);
     }
 
-    // reboot
+    reboot();
+}
+
+#[cfg(feature = "panic_handler")]
+fn reboot() -> ! {
     unsafe {
         OSForceFullRelaunch();
         SYSLaunchMenu();